@@ -1,6 +1,46 @@
+use std::collections::HashSet;
+use std::fmt;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
+use std::process::ExitCode;
+
+// the most sides a puzzle can have, so that its letters fit in the bits of a u16
+const MAX_SIDES: usize = 5;
+
+// what can go wrong while setting up or solving a puzzle
+#[derive(Debug)]
+enum Error {
+    // the word list could not be read
+    Io(String, io::Error),
+    // an argument that is not a number
+    Parse(String),
+    // arguments that do not make a puzzle
+    Invalid(String),
+}
+
+impl Error {
+    // the exit code, after sysexits.h
+    fn exit_code(&self) -> u8 {
+        match self {
+            Error::Invalid(_) => 64,
+            Error::Parse(_) => 65,
+            Error::Io(_, e) if e.kind() == io::ErrorKind::NotFound => 66,
+            Error::Io(..) => 74,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(file, e) => write!(f, "Error reading {}: {}", file, e),
+            Error::Parse(message) | Error::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
 
 #[derive(Debug)]
 struct Side {
@@ -25,10 +65,8 @@ impl Side {
             if last_char == c {
                 return false;
             }
-            if last_char == self.a || last_char == self.b || last_char == self.c {
-                if c == self.a || c == self.b || c == self.c {
-                    return false;
-                }
+            if self.has_letter(last_char) && self.has_letter(c) {
+                return false;
             }
             last_char = c;
         }
@@ -38,31 +76,6 @@ impl Side {
     fn has_letter(&self, letter: char) -> bool {
         self.a == letter || self.b == letter || self.c == letter
     }
-
-    fn any_missing_letter(&self, word1: &str, word2: &str) -> bool {
-        let mut has_a = false;
-        let mut has_b = false;
-        let mut has_c = false;
-        for c in word1.chars() {
-            if c == self.a {
-                has_a = true;
-            } else if c == self.b {
-                has_b = true;
-            } else if c == self.c {
-                has_c = true;
-            }
-        }
-        for c in word2.chars() {
-            if c == self.a {
-                has_a = true;
-            } else if c == self.b {
-                has_b = true;
-            } else if c == self.c {
-                has_c = true;
-            }
-        }
-        !has_a || !has_b || !has_c
-    }
 }
 
 
@@ -73,16 +86,6 @@ where P: AsRef<Path>, {
     Ok(io::BufReader::new(file).lines())
 }
 
-// two words are a valid pair if the last letter of the first word is the same as the first letter
-// of the second word and every let
-fn valid_word_pair(word1: &str, word2: &str) -> bool {
-    let last_char = word1.chars().last().unwrap();
-    let first_char = word2.chars().next().unwrap();
-    last_char == first_char
-}
-
-
-
 struct Puzzle {
     sides : Vec<Side>
 }
@@ -92,13 +95,22 @@ impl Puzzle {
         Puzzle { sides: Vec::new() }
     }
 
-    fn add_side(&mut self, side_chars: &str) {
-        if side_chars.len() != 3 {
-            panic!("Side must have exactly 3 characters");
-        }
+    // adds a side of three letters, none of which may be on the puzzle already
+    fn add_side(&mut self, side_chars: &str) -> Result<(), Error> {
         let chars: Vec<char> = side_chars.chars().collect();
-        let side = Side::new(chars[0], chars[1], chars[2]);
-        self.sides.push(side);
+        if chars.len() != 3 || !chars.iter().all(|c| c.is_ascii_alphabetic()) {
+            return Err(Error::Invalid(format!("Side {} must have exactly 3 letters", side_chars)));
+        }
+        if self.sides.len() == MAX_SIDES {
+            return Err(Error::Invalid(format!("A puzzle has at most {} sides", MAX_SIDES)));
+        }
+        for (i, c) in chars.iter().enumerate() {
+            if chars[..i].contains(c) || self.sides.iter().any(|side| side.has_letter(*c)) {
+                return Err(Error::Invalid(format!("{} appears more than once in the puzzle", c)));
+            }
+        }
+        self.sides.push(Side::new(chars[0], chars[1], chars[2]));
+        Ok(())
     }
 
     fn is_valid_word(&self, word: &str) -> bool {
@@ -122,46 +134,180 @@ impl Puzzle {
         true
     }
 
-    // two words are a valid pair if the last letter of the first word is the same as the first
-    // letter and evert letter of the puzzle is in either of the words
-    fn valid_word_pair(&self, word1: &str, word2: &str) -> bool {
-        if valid_word_pair(word1, word2) {
-            if self.sides.iter().any(|side| side.any_missing_letter(word1, word2)) {
-                return false;
+    // the bit for each letter of the puzzle, in the order of the sides
+    fn letter_mask(&self, c: char) -> u16 {
+        let mut bit = 0;
+        for side in &self.sides {
+            for letter in [side.a, side.b, side.c] {
+                if letter == c {
+                    return 1 << bit;
+                }
+                bit += 1;
             }
-            return true;
         }
-        false
+        0
+    }
+
+    // the letters of the word as a bitmask
+    fn word_mask(&self, word: &str) -> u16 {
+        word.chars().fold(0, |mask, c| mask | self.letter_mask(c))
     }
 
-    // print all words from the file that are valid
-    fn print_valid_words(&self, filename: &str) {
+    // the mask with every letter of the puzzle
+    fn full_mask(&self) -> u16 {
+        (1 << (self.sides.len() * 3)) - 1
+    }
+
+    // all words from the file that are valid
+    fn valid_words(&self, filename: &str) -> Result<Vec<String>, Error> {
         let mut words = Vec::new();
-        if let Ok(lines) = read_lines(filename) {
-            for line in lines {
-                if let Ok(word) = line {
-                    let word = word.trim().to_uppercase();
-                    if word.len() < 3 {
-                        continue;
-                    }
-                    if self.is_valid_word(&word) {
-                        words.push(word.clone());
-                    }
-                }
+        let lines = read_lines(filename).map_err(|e| Error::Io(filename.to_string(), e))?;
+        for word in lines {
+            let word = word.map_err(|e| Error::Io(filename.to_string(), e))?;
+            let word = word.trim().to_uppercase();
+            if word.len() < 3 {
+                continue;
+            }
+            if self.is_valid_word(&word) && !words.contains(&word) {
+                words.push(word);
             }
-        } else {
-            println!("Error reading file");
         }
-        for word1 in words.iter() {
-            for word2 in words.iter() {
-                if *word1 != *word2 && self.valid_word_pair(word1, word2) {
-                    println!("{} {}", word1, word2);
-                }
+        Ok(words)
+    }
+
+    // the chains with the fewest words (but no more than max_words) that use every letter of the
+    // puzzle. Each word starts with the last letter of the previous one. The chains are ranked by
+    // the total number of letters, shortest first.
+    fn solve(&self, words: &[String], max_words: usize) -> Vec<Vec<String>> {
+        let chain_words: Vec<ChainWord> = words.iter()
+            .map(|word| ChainWord {
+                word: word.clone(),
+                mask: self.word_mask(word),
+                first: self.letter_mask(word.chars().next().unwrap()),
+                last: self.letter_mask(word.chars().last().unwrap()),
+            })
+            .collect();
+        let mut search = ChainSearch {
+            words: &chain_words,
+            full: self.full_mask(),
+            dead: HashSet::new(),
+            chain: Vec::new(),
+            solutions: Vec::new(),
+        };
+        for depth in 1..=max_words {
+            for (i, word) in chain_words.iter().enumerate() {
+                search.chain.push(i);
+                search.extend(word.mask, depth - 1);
+                search.chain.pop();
+            }
+            if !search.solutions.is_empty() {
+                break;
             }
         }
+        let mut solutions: Vec<Vec<String>> = search.solutions.iter()
+            .map(|chain| chain.iter().map(|i| chain_words[*i].word.clone()).collect())
+            .collect();
+        solutions.sort_by_key(|chain: &Vec<String>| (chain.iter().map(|w| w.len()).sum::<usize>(), chain.clone()));
+        solutions
     }
 }
 
+// a valid word, with its letters as a bitmask and the bits of its first and last letters
+struct ChainWord {
+    word: String,
+    mask: u16,
+    first: u16,
+    last: u16,
+}
+
+// depth first search for chains of words that cover every letter
+struct ChainSearch<'a> {
+    words: &'a [ChainWord],
+    // every letter of the puzzle
+    full: u16,
+    // (last letter, letters covered, words left) states known to lead nowhere
+    dead: HashSet<(u16, u16, usize)>,
+    // the indices of the words in the chain being built
+    chain: Vec<usize>,
+    solutions: Vec<Vec<usize>>,
+}
+
+impl ChainSearch<'_> {
+    // tries to complete the current chain, whose letters are `mask`, with up to `left` more words.
+    // returns true when at least one solution was found
+    fn extend(&mut self, mask: u16, left: usize) -> bool {
+        if mask == self.full {
+            self.solutions.push(self.chain.clone());
+            return true;
+        }
+        let last = self.words[*self.chain.last().unwrap()].last;
+        if left == 0 || self.dead.contains(&(last, mask, left)) {
+            return false;
+        }
+        let mut found = false;
+        for i in 0..self.words.len() {
+            let next = &self.words[i];
+            if next.first != last || next.mask | mask == mask {
+                continue;
+            }
+            let next_mask = next.mask | mask;
+            // only the last word may finish the puzzle, so that every chain has exactly the
+            // number of words being searched for
+            if left > 1 && next_mask == self.full {
+                continue;
+            }
+            if left == 1 && next_mask != self.full {
+                continue;
+            }
+            self.chain.push(i);
+            found |= self.extend(next_mask, left - 1);
+            self.chain.pop();
+        }
+        if !found {
+            self.dead.insert((last, mask, left));
+        }
+        found
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 6 {
+        let name = args.first().map_or("lbpuzzle", |name| name.as_str());
+        return Err(Error::Invalid(format!("Usage: {} <side1> <side2> <side3> <side4> <file> [max-words]", name)));
+    }
+    let mut puzzle = Puzzle::new();
+    for side in &args[1..5] {
+        puzzle.add_side(&side.to_uppercase())?;
+    }
+    let filename = &args[5];
+    let max_words = match args.get(6).map(|n| n.parse::<usize>()) {
+        None => 4,
+        Some(Ok(n)) if n > 0 => n,
+        Some(Ok(_)) => return Err(Error::Invalid("max-words must be at least 1".to_string())),
+        Some(Err(_)) => return Err(Error::Parse("max-words must be a positive number".to_string())),
+    };
+    let words = puzzle.valid_words(filename)?;
+    let solutions = puzzle.solve(&words, max_words);
+    if solutions.is_empty() {
+        println!("No solution with up to {} words", max_words);
+    }
+    for solution in solutions {
+        println!("{}", solution.join(" "));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,29 +322,47 @@ mod tests {
         assert!(!side.is_valid_word("CDADAB"));
     }
 
+    #[test]
+    fn test_solve() {
+        let mut puzzle = Puzzle::new();
+        puzzle.add_side("ABC").unwrap();
+        puzzle.add_side("DEF").unwrap();
+        puzzle.add_side("GHI").unwrap();
+        puzzle.add_side("JKL").unwrap();
+        let words: Vec<String> = ["ADGJ", "JBEH", "HCFKIL", "HCFKILA"].iter().map(|w| w.to_string()).collect();
+        assert!(words.iter().all(|w| puzzle.is_valid_word(w)));
+        assert!(puzzle.solve(&words, 2).is_empty());
+        assert_eq!(puzzle.solve(&words, 4), vec![
+            vec!["ADGJ", "JBEH", "HCFKIL"],
+            vec!["ADGJ", "JBEH", "HCFKILA"],
+            vec!["HCFKILA", "ADGJ", "JBEH"],
+            vec!["JBEH", "HCFKILA", "ADGJ"],
+        ]);
+
+        let mut words = words;
+        words.push("ADGJBEH".to_string());
+        assert_eq!(puzzle.solve(&words, 4)[0], vec!["ADGJBEH", "HCFKIL"]);
+    }
+
     #[test]
     fn test_puzzle1() {
         let mut puzzle = Puzzle::new();
-        puzzle.add_side("MBO");
-        puzzle.add_side("VIG");
-        puzzle.add_side("DAE");
-        puzzle.add_side("SUR");
+        puzzle.add_side("MBO").unwrap();
+        puzzle.add_side("VIG").unwrap();
+        puzzle.add_side("DAE").unwrap();
+        puzzle.add_side("SUR").unwrap();
         assert!(!puzzle.is_valid_word("ABELMOSCHUS"));
     }
 
-}
-
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() < 6 {
-        println!("Usage: {} <side1> <side2> <side3> <side4> <file>", args[0]);
-        return;
-    }
-    let mut puzzle = Puzzle::new();
-    for i in 1..5 {
-        puzzle.add_side(&args[i]);
+    #[test]
+    fn test_bad_sides() {
+        let mut puzzle = Puzzle::new();
+        assert!(puzzle.add_side("AB").is_err());
+        assert!(puzzle.add_side("AB1").is_err());
+        assert!(puzzle.add_side("ABA").is_err());
+        puzzle.add_side("ABC").unwrap();
+        assert!(puzzle.add_side("CDE").is_err());
+        assert_eq!(puzzle.sides.len(), 1);
     }
-    let filename = &args[5];
-    puzzle.print_valid_words(filename);
 
 }
@@ -0,0 +1,169 @@
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::process::ExitCode;
+
+// the shortest word the puzzle accepts
+const MIN_WORD_LEN: usize = 4;
+
+// the bonus for a word that uses all seven letters
+const PANGRAM_BONUS: usize = 7;
+
+// the bit for a lower case letter
+fn letter_bit(c: char) -> u32 {
+    1 << (c as u32 - 'a' as u32)
+}
+
+// the letters of a lower case word as a bitmask, or None when the word has anything but a-z
+fn word_mask(word: &str) -> Option<u32> {
+    let mut mask = 0;
+    for c in word.chars() {
+        if !c.is_ascii_lowercase() {
+            return None;
+        }
+        mask |= letter_bit(c);
+    }
+    Some(mask)
+}
+
+#[derive(Debug)]
+struct SpellingBee {
+    // the letter every word must use
+    center: char,
+    // the center letter and the six outer letters
+    letters: u32,
+}
+
+impl SpellingBee {
+    fn new(center: char, outer: &str) -> Option<Self> {
+        let center = center.to_ascii_lowercase();
+        let letters = word_mask(&outer.to_lowercase())? | word_mask(&center.to_string())?;
+        if outer.len() != 6 || letters.count_ones() != 7 {
+            return None;
+        }
+        Some(SpellingBee { center, letters })
+    }
+
+    // a word is valid if it is long enough, uses the center letter and nothing but the seven
+    // letters of the puzzle
+    fn is_valid_word(&self, word: &str) -> bool {
+        if word.len() < MIN_WORD_LEN {
+            return false;
+        }
+        match word_mask(word) {
+            Some(mask) => mask & letter_bit(self.center) != 0 && mask & !self.letters == 0,
+            None => false,
+        }
+    }
+
+    // a pangram uses every one of the seven letters
+    fn is_pangram(&self, word: &str) -> bool {
+        word_mask(word) == Some(self.letters)
+    }
+
+    // the official scoring: four letter words are worth one point, longer words one point per
+    // letter, and pangrams seven points more
+    fn score(&self, word: &str) -> usize {
+        let score = if word.len() == MIN_WORD_LEN { 1 } else { word.len() };
+        if self.is_pangram(word) {
+            score + PANGRAM_BONUS
+        } else {
+            score
+        }
+    }
+
+    // the valid words from the file, best scoring first
+    fn solve(&self, filename: &str) -> io::Result<Vec<String>> {
+        let mut words = Vec::new();
+        for word in read_lines(filename)? {
+            let word = word?.trim().to_lowercase();
+            if self.is_valid_word(&word) && !words.contains(&word) {
+                words.push(word);
+            }
+        }
+        words.sort_by(|a, b| self.score(b).cmp(&self.score(a)).then_with(|| a.cmp(b)));
+        Ok(words)
+    }
+}
+
+// reads a file line by line
+fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+where P: AsRef<Path>, {
+    let file = File::open(filename)?;
+    Ok(io::BufReader::new(file).lines())
+}
+
+// the exit code for bad arguments, after sysexits.h
+const EX_USAGE: u8 = 64;
+
+// the exit code for a word list that cannot be read: 66 when it is missing, 74 otherwise
+fn io_exit_code(e: &io::Error) -> u8 {
+    if e.kind() == io::ErrorKind::NotFound { 66 } else { 74 }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 4 || args[1].chars().count() != 1 {
+        let name = args.first().map_or("spellingbee", |name| name.as_str());
+        eprintln!("Usage: {} <center-letter> <six-outer-letters> <file>", name);
+        return ExitCode::from(EX_USAGE);
+    }
+    let center = args[1].chars().next().unwrap();
+    let puzzle = match SpellingBee::new(center, &args[2]) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("The puzzle needs seven different letters: one in the center and six around it");
+            return ExitCode::from(EX_USAGE);
+        }
+    };
+    let words = match puzzle.solve(&args[3]) {
+        Ok(words) => words,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", args[3], e);
+            return ExitCode::from(io_exit_code(&e));
+        }
+    };
+    let mut total = 0;
+    for word in &words {
+        let score = puzzle.score(word);
+        total += score;
+        if puzzle.is_pangram(word) {
+            println!("{:>3} {} (pangram)", score, word.to_uppercase());
+        } else {
+            println!("{:>3} {}", score, word);
+        }
+    }
+    println!("{} words, {} points", words.len(), total);
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_word() {
+        let puzzle = SpellingBee::new('t', "aceilp").unwrap();
+        assert!(puzzle.is_valid_word("tact"));
+        assert!(!puzzle.is_valid_word("capitalize"));
+        assert!(!puzzle.is_valid_word("tip"));
+        assert!(!puzzle.is_valid_word("place"));
+        assert!(!puzzle.is_valid_word("Tact"));
+    }
+
+    #[test]
+    fn test_score() {
+        let puzzle = SpellingBee::new('t', "aceilp").unwrap();
+        assert_eq!(puzzle.score("tact"), 1);
+        assert_eq!(puzzle.score("tactic"), 6);
+        assert!(puzzle.is_pangram("plicate"));
+        assert_eq!(puzzle.score("plicate"), 7 + PANGRAM_BONUS);
+    }
+
+    #[test]
+    fn test_puzzle_letters() {
+        assert!(SpellingBee::new('t', "aceil").is_none());
+        assert!(SpellingBee::new('t', "aceilt").is_none());
+        assert!(SpellingBee::new('T', "ACEILP").is_some());
+    }
+}
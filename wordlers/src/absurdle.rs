@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::collection::WordCollection;
+use crate::compare::pattern_code;
+use crate::feedback::{Feedback, Tile};
+use crate::word::Word;
+
+/// an adversarial wordle that never commits to a target. Every guess gets the response that keeps
+/// as many of the possible answers alive as it can, so the player has to corner it.
+#[derive(Debug, Clone)]
+pub struct Absurdle {
+    /// the answers consistent with every response given so far.
+    candidates: WordCollection,
+}
+
+impl Absurdle {
+    pub fn new(answers: WordCollection) -> Absurdle {
+        Absurdle {
+            candidates: answers
+        }
+    }
+
+    /// the answers it could still pick.
+    pub fn candidates(&self) -> &WordCollection {
+        &self.candidates
+    }
+
+    /// responds to the guess. The candidates are split by the response [`wordle_compare`] would
+    /// give for each of them, and the largest bucket survives. Ties go to the response with the
+    /// fewest greens, then the fewest yellows.
+    ///
+    /// [`wordle_compare`]: crate::wordle_compare
+    pub fn guess(&mut self, guess: &Word) -> Feedback {
+        let mut buckets: HashMap<Feedback, Vec<Word>> = HashMap::new();
+        for word in self.candidates.words() {
+            buckets.entry(Feedback::from_code(pattern_code(word, guess), guess.len())).or_default().push(*word);
+        }
+        let count = |response: &Feedback, tile: Tile| response.tiles().iter().filter(|t| **t == tile).count();
+        let (response, words) = buckets.into_iter()
+            .max_by(|(r1, w1), (r2, w2)| {
+                w1.len().cmp(&w2.len())
+                    .then_with(|| count(r2, Tile::Green).cmp(&count(r1, Tile::Green)))
+                    .then_with(|| count(r2, Tile::Yellow).cmp(&count(r1, Tile::Yellow)))
+                    .then_with(|| r2.code().cmp(&r1.code()))
+            })
+            .unwrap_or_else(|| (Feedback::new(&vec![Tile::Gray; guess.len()]), Vec::new()));
+        self.candidates = WordCollection::from_words(words);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absurdle_keeps_largest_bucket() {
+        let words = WordCollection::from_lines(["bills", "fills", "hills", "crane"], 5);
+        let mut game = Absurdle::new(words);
+        assert_eq!(game.guess(&Word::new("crane").unwrap()).to_string(), "NNNNN");
+        assert_eq!(game.candidates().len(), 3);
+        assert_eq!(game.guess(&Word::new("hills").unwrap()).to_string(), "NGGGG");
+        assert_eq!(game.candidates().len(), 2);
+        game.guess(&Word::new("fills").unwrap());
+        assert!(game.guess(&Word::new("bills").unwrap()).is_solved());
+    }
+}
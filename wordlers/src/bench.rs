@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::thread;
+
+use crate::compare::pattern_code;
+use crate::feedback::Feedback;
+use crate::opening::Opening;
+use crate::solver::Solver;
+use crate::word::Word;
+
+/// the number of guesses wordle allows.
+pub const MAX_GUESSES: usize = 6;
+
+/// the number of guesses after which a simulated game is abandoned.
+pub const GIVE_UP_AFTER: usize = 20;
+
+/// plays one game against `target`, using [`pattern_code`] as the oracle, and returns the
+/// number of guesses it took, or None when the solver ran out of words or the target is not as
+/// long as them.
+pub fn play_game(solver: &mut Solver, opening: &Opening, target: &Word) -> Option<usize> {
+    play_remembering(solver, opening, target, &mut HashMap::new())
+}
+
+/// the guesses suggested so far, keyed by the guesses and responses that led to them. The
+/// solver only depends on those, so games that start out alike can share its suggestions.
+type Suggestions = HashMap<Vec<(Word, Feedback)>, Option<Word>>;
+
+/// [`play_game`], looking the solver's suggestions up in `suggestions` when possible.
+fn play_remembering(solver: &mut Solver, opening: &Opening, target: &Word, suggestions: &mut Suggestions) -> Option<usize> {
+    solver.reset();
+    let mut history = Vec::new();
+    let mut known = 0;
+    for turn in 0..GIVE_UP_AFTER {
+        let guess = match opening.opener(turn, known) {
+            Some(guess) => guess,
+            None => (*suggestions.entry(history.clone()).or_insert_with(|| solver.suggest()))?,
+        };
+        let response = Feedback::from_code(pattern_code(target, &guess), guess.len());
+        if response.is_solved() {
+            return Some(turn + 1);
+        }
+        solver.feedback(&guess, &response).ok()?;
+        known += response.known_count();
+        history.push((guess, response));
+    }
+    None
+}
+
+/// plays a game against every one of the `targets`, spreading the games over all cores.
+pub fn play_games(solver: &Solver, opening: &Opening, targets: &[Word]) -> BenchReport {
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let chunk_size = targets.len().div_ceil(threads).max(1);
+    let games = thread::scope(|scope| {
+        let handles: Vec<_> = targets.chunks(chunk_size)
+            .map(|chunk| {
+                let mut solver = solver.clone();
+                let mut suggestions = Suggestions::new();
+                scope.spawn(move || {
+                    chunk.iter()
+                        .map(|target| (*target, play_remembering(&mut solver, opening, target, &mut suggestions)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    BenchReport { games }
+}
+
+/// the outcome of [`play_games`].
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    /// the number of guesses taken for each target, None for the games given up on.
+    pub games: Vec<(Word, Option<usize>)>,
+}
+
+impl BenchReport {
+    /// the average number of guesses over the games that were solved.
+    pub fn average(&self) -> f64 {
+        let solved: Vec<usize> = self.games.iter().filter_map(|(_, guesses)| *guesses).collect();
+        if solved.is_empty() {
+            return 0.0;
+        }
+        solved.iter().sum::<usize>() as f64 / solved.len() as f64
+    }
+
+    /// the number of games solved in exactly `i` guesses, at index `i`.
+    pub fn histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![0; GIVE_UP_AFTER + 1];
+        for guesses in self.games.iter().filter_map(|(_, guesses)| *guesses) {
+            histogram[guesses] += 1;
+        }
+        histogram
+    }
+
+    /// the most guesses any game took, or None when a game was given up on. This is the worst
+    /// case the solver guarantees over the targets.
+    pub fn most_guesses(&self) -> Option<usize> {
+        self.games.iter().map(|(_, guesses)| *guesses).max_by_key(|guesses| guesses.unwrap_or(usize::MAX)).flatten()
+    }
+
+    /// the targets that took more than [`MAX_GUESSES`] or were never found.
+    pub fn failures(&self) -> Vec<Word> {
+        self.games.iter()
+            .filter(|(_, guesses)| guesses.is_none_or(|g| g > MAX_GUESSES))
+            .map(|(word, _)| *word)
+            .collect()
+    }
+
+    /// the `n` targets that needed the most guesses, worst first.
+    pub fn worst(&self, n: usize) -> Vec<(Word, Option<usize>)> {
+        let mut games = self.games.clone();
+        games.sort_by_key(|(word, guesses)| (std::cmp::Reverse(guesses.unwrap_or(usize::MAX)), word.to_string()));
+        games.truncate(n);
+        games
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::WordCollection;
+
+    #[test]
+    fn test_run() {
+        let words = WordCollection::from_lines(["crane", "slate", "brine", "shine", "prone", "adieu"], 5);
+        let solver = Solver::new(words.clone());
+        let report = play_games(&solver, &Opening::Solver, words.words());
+        assert_eq!(report.games.len(), 6);
+        assert!(report.failures().is_empty());
+        assert_eq!(report.histogram()[1], 1);
+        assert!(report.average() >= 1.0);
+        assert!(report.most_guesses().is_some_and(|most| most <= MAX_GUESSES));
+    }
+}
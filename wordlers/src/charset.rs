@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// a set for alphabetical characters. Considering the nature of the problem, we can use an
+/// unsigned integer to represent the set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharSet {
+    /// the set of characters
+    chars: u32,
+}
+
+impl CharSet {
+    pub fn new() -> CharSet {
+        CharSet { chars: 0 }
+    }
+
+    pub fn new_full() -> CharSet {
+        CharSet { chars: 0xFFFFFFFF }
+    }
+
+    pub fn add(&mut self, c: char) {
+        self.chars |= 1 << (c as u32 - 'a' as u32);
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        (self.chars & (1 << (c as u32 - 'a' as u32))) != 0
+    }
+
+    pub fn remove(&mut self, c: char) {
+        self.chars &= !(1 << (c as u32 - 'a' as u32));
+    }
+
+    pub fn is_subset_of(&self, other: &CharSet) -> bool {
+        (self.chars & other.chars) == self.chars
+    }
+
+    /// keeps `c` only, if it is there at all.
+    pub fn remove_others(&mut self, c: char) {
+        self.chars &= 1 << (c as u32 - 'a' as u32);
+    }
+
+    /// true when no letter is left.
+    pub fn is_empty(&self) -> bool {
+        self.chars & 0x3FFFFFF == 0
+    }
+}
+
+impl Default for CharSet {
+    fn default() -> Self {
+        CharSet::new()
+    }
+}
+
+impl fmt::Display for CharSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut chars = vec![];
+        for i in 0..26u8 {
+            if self.chars & (1 << i) != 0 {
+                chars.push((i + b'a') as char);
+            }
+        }
+        write!(f, "<{}>", chars.iter().collect::<String>())
+    }
+}
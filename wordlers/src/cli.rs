@@ -0,0 +1,236 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::builder::RangedU64ValueParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use wordlers::{Objective, Strategy, Tile, MAX_WORD_LEN};
+
+/// solves and plays wordle, and friends. Without a command, it solves wordle.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    #[command(flatten)]
+    pub lists: ListArgs,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// the word lists and how the solver uses them, taken by every command.
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Word lists")]
+pub struct ListArgs {
+    /// the words accepted as guesses [default: $WORDLERS_WORDS, else the sgb list or the bundled sample]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub words: Option<PathBuf>,
+    /// the words that can be the answer [default: $WORDLERS_ANSWERS, else the guesses]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+    /// the number of letters in each word
+    #[arg(long, global = true, default_value_t = 5, value_name = "N",
+          value_parser = RangedU64ValueParser::<usize>::new().range(1..=MAX_WORD_LEN as u64))]
+    pub length: usize,
+    /// how the solver picks its guesses: frequency, entropy or minimax
+    #[arg(long, global = true, default_value_t = Strategy::default())]
+    pub strategy: Strategy,
+    /// follows the decision tree saved in FILE by the tree command, and the strategy off it
+    #[arg(long, global = true, value_name = "FILE")]
+    pub tree: Option<PathBuf>,
+}
+
+/// where a solving game is recorded.
+#[derive(Debug, Clone, Args)]
+pub struct SessionArgs {
+    /// records the game in FILE [default: $XDG_STATE_HOME/wordlers/session.jsonl]
+    #[arg(long, value_name = "FILE")]
+    pub session: Option<PathBuf>,
+    /// carries on with the game recorded in FILE
+    #[arg(long, value_name = "FILE", conflicts_with = "session")]
+    pub resume: Option<PathBuf>,
+}
+
+/// how reports are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+/// the colour pictures are drawn in.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Ink {
+    Green,
+    Yellow,
+}
+
+impl From<Ink> for Tile {
+    fn from(ink: Ink) -> Tile {
+        match ink {
+            Ink::Green => Tile::Green,
+            Ink::Yellow => Tile::Yellow,
+        }
+    }
+}
+
+/// a number of things, or all: the solutions `shapes` prints, the guesses `tree` tries.
+#[derive(Debug, Clone, Copy)]
+pub struct Limit(pub Option<usize>);
+
+impl FromStr for Limit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Limit, String> {
+        match s {
+            "all" => Ok(Limit(None)),
+            _ => s.parse().map(|n| Limit(Some(n))).map_err(|_| "takes a number or all".to_string()),
+        }
+    }
+}
+
+/// parses a number that is at least 1.
+fn positive(s: &str) -> Result<usize, String> {
+    match s.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err("takes a number from 1 up".to_string()),
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// suggests guesses for wordle and reads the response to each, as G/Y/N letters or squares
+    Solve {
+        /// the first word to play
+        start: Option<String>,
+        #[command(flatten)]
+        session: SessionArgs,
+    },
+    /// solves wordle without hard mode: up to four words find the letters first
+    Soft {
+        #[command(flatten)]
+        session: SessionArgs,
+    },
+    /// shows how many words were left after each step of a recorded game
+    Replay {
+        /// the file the game was recorded in
+        file: PathBuf,
+        /// prints the report as text or as JSON
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// plays wordle against a random answer
+    #[command(alias = "wordle")]
+    Play {
+        /// every guess must use the hints given so far
+        #[arg(long)]
+        hard: bool,
+        /// picks the answer with this seed, to play the same game again
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// plays absurdle, where the answer dodges the guesses for as long as it can
+    Absurdle,
+    /// solves every answer and reports how many guesses it took
+    Bench {
+        /// soft, or the first word to play
+        opening: Option<String>,
+        /// prints the report as text or as JSON
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// benches every strategy on every answer, to compare their average and worst case
+    Compare {
+        /// soft, or the first word to play
+        opening: Option<String>,
+        /// prints the report as text or as JSON
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// solves several boards at once, reading one response per board
+    Multi {
+        /// the number of boards
+        #[arg(value_parser = positive)]
+        boards: usize,
+    },
+    /// solves four boards at once
+    Quordle,
+    /// solves eight boards at once
+    Octordle,
+    /// solves sixteen boards at once
+    Sedecordle,
+    /// plays cows and bulls against a random answer
+    #[command(alias = "cowsandbulls")]
+    Cb {
+        /// picks the answer with this seed, to play the same game again
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// solves cows and bulls, reading the bulls and the cows for each guess, like 2 1
+    CbSolve,
+    /// finds distinct words that draw the rows on the board of TARGET
+    Shapes {
+        /// the answer of the board
+        target: String,
+        /// the response wanted on each row, like NNGGG
+        #[arg(required = true)]
+        rows: Vec<String>,
+        /// the number of solutions to print, or all
+        #[arg(long, default_value = "1", value_name = "N")]
+        solutions: Limit,
+        /// the guesses must be valid in hard mode
+        #[arg(long)]
+        hard: bool,
+        /// prints the solutions made of the most common words first
+        #[arg(long)]
+        rank: bool,
+        /// prints the report as text or as JSON
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// draws text, ASCII art or a PBM bitmap on as many boards of TARGET as it takes
+    Draw {
+        /// the answer of the boards
+        target: String,
+        /// the text to draw
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        text: Vec<String>,
+        /// draws the PBM bitmap or ASCII art in FILE instead of text
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// the colour text and bitmaps are drawn in
+        #[arg(long, value_enum, default_value = "green")]
+        ink: Ink,
+        /// the guesses must be valid in hard mode
+        #[arg(long)]
+        hard: bool,
+        /// picks the most common words
+        #[arg(long)]
+        rank: bool,
+    },
+    /// works out the decision tree that solves every answer in the fewest guesses, and compares the
+    /// strategy with it
+    Tree {
+        /// the first word to play, instead of the best one
+        start: Option<String>,
+        /// what the tree keeps down: the total number of guesses, or the most for any answer
+        #[arg(long, default_value_t = Objective::default())]
+        objective: Objective,
+        /// tries only the N guesses that leave the fewest words on average in each state; with
+        /// all, the tree is provably optimal but long lists take a long time
+        #[arg(long, default_value = "all", value_name = "N")]
+        breadth: Limit,
+        /// saves the tree in FILE, to solve with --tree
+        #[arg(long, value_name = "FILE")]
+        save: Option<PathBuf>,
+        /// prints the report as text or as JSON
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// replays a shared result, asking for the word played on each row
+    Import,
+    /// guesses the answer from several shared results, read until the end of the input
+    Infer {
+        /// prints the report as text or as JSON
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+}
@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+use rand::prelude::*;
+
+use crate::compare::{pattern_code, pattern_count};
+use crate::error::Error;
+use crate::state::WordleState;
+use crate::word::Word;
+
+/// the list in `words/sample-words.txt`, compiled in with the `bundled` feature. It is a sample of
+/// 213 common five letter words, not the sgb list.
+#[cfg(feature = "bundled")]
+const BUNDLED_WORDS: &str = include_str!("../words/sample-words.txt");
+
+/// a pair of character and the number of times it appears in the word list.
+#[derive(Debug)]
+struct CharCount {
+    c : u32,
+    count : u32
+}
+
+/// the allowed collection of words.
+#[derive(Debug, Clone, Default)]
+pub struct WordCollection {
+    words: Vec<Word>,
+    /// the number of letters in each word.
+    word_len: usize,
+}
+
+impl WordCollection {
+    /// reads the words with `word_len` letters from the file.
+    pub fn new(path: impl AsRef<Path>, word_len: usize) -> Result<WordCollection, Error> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::file(path, e))?;
+        let lines = io::BufReader::new(file).lines()
+            .collect::<io::Result<Vec<String>>>()
+            .map_err(|e| Error::file(path, e))?;
+        Ok(WordCollection::from_lines(lines, word_len))
+    }
+
+    /// the words with `word_len` letters from the sample compiled into the crate. It only has
+    /// five letter words.
+    #[cfg(feature = "bundled")]
+    pub fn bundled(word_len: usize) -> WordCollection {
+        WordCollection::from_lines(BUNDLED_WORDS.lines(), word_len)
+    }
+
+    /// keeps the lower case words with `word_len` letters from the given lines.
+    pub fn from_lines<I, S>(lines: I, word_len: usize) -> WordCollection
+    where I: IntoIterator<Item = S>, S: AsRef<str> {
+        let mut words = Vec::new();
+        for line in lines {
+            let x = line.as_ref().trim();
+            if x.len() != word_len {
+                continue;
+            }
+            if let Ok(word) = Word::new(x) {
+                words.push(word);
+            }
+        }
+        WordCollection {
+            words,
+            word_len
+        }
+    }
+
+    /// a collection of the given words, which must all be of the same length.
+    pub fn from_words(words: Vec<Word>) -> WordCollection {
+        let word_len = words.first().map_or(0, |w| w.len());
+        WordCollection {
+            words,
+            word_len
+        }
+    }
+
+    /// the number of letters in each word.
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// returns a random word from the collection.
+    /// Useful for playing wordle/cows and bulls.
+    pub fn get_random_word(&self) -> String {
+        self.random_word(&mut thread_rng())
+    }
+
+    /// [`WordCollection::get_random_word`] drawn from `rng`, to replay the same game with a
+    /// seeded generator.
+    pub fn random_word(&self, rng: &mut impl Rng) -> String {
+        let index = rng.gen_range(0..self.words.len());
+        self.words[index].to_string()
+    }
+
+    /// returns true if the given word is in the collection.
+    pub fn contains_word(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        self.words.iter().any(|w| w.to_string() == word)
+    }
+
+    /// returns the best guess for the next word for wordle, or None when no word is left.
+    pub fn get_best_word(&self) -> Option<Word> {
+        if self.words.is_empty() {
+            return None;
+        }
+        let mut vec = Vec::new();
+        for c in 0..26 {
+            vec.push(CharCount {
+                c: c as u32,
+                count: 0
+            });
+        }
+        for word in &self.words {
+            for c in word.to_string().chars() {
+                vec[c as usize - 'a' as usize].count += 1;
+            }
+        }
+        vec.sort_by_key(|a| a.count);
+        let mut scores = Vec::new();
+        for c in 0..26 {
+            let i = vec.iter().position(|x| x.c == c as u32).unwrap();
+            scores.push(i);
+        }
+        let mut best_word = self.words[0];
+        let mut score = best_word.score(&scores);
+        for word in self.words.iter().skip(1) {
+            let new_score = word.score(&scores);
+            if new_score > score {
+                best_word = *word;
+                score = new_score;
+            }
+        }
+        Some(best_word)
+    }
+
+    /// the expected information (in bits) gained about the words in this collection by guessing
+    /// `guess`. A guess of another length still gets a response, as in [`wordle_compare`].
+    ///
+    /// [`wordle_compare`]: crate::wordle_compare
+    pub fn entropy(&self, guess: &Word) -> f64 {
+        let mut buckets = vec![0usize; pattern_count(guess.len())];
+        for word in &self.words {
+            buckets[pattern_code(word, guess)] += 1;
+        }
+        let total = self.words.len() as f64;
+        buckets.iter()
+            .filter(|count| **count > 0)
+            .map(|count| {
+                let p = *count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// returns the word from `guesses` whose response splits this collection the most evenly,
+    /// that is, the one with the highest entropy. Words that could still be the answer win ties.
+    pub fn get_max_entropy_word(&self, guesses: &WordCollection) -> Option<Word> {
+        match self.words.len() {
+            0 => return None,
+            1 | 2 => return Some(self.words[0]),
+            _ => {}
+        }
+        let mut best_word = self.words[0];
+        let mut best_score = self.entropy(&best_word);
+        for word in self.words.iter().chain(guesses.words.iter()) {
+            let score = self.entropy(word);
+            if score > best_score + 1e-9 {
+                best_word = *word;
+                best_score = score;
+            }
+        }
+        Some(best_word)
+    }
+
+    /// how `guess` splits this collection in the worst case and on average: the number of words
+    /// giving the most common response, and the sum of the squared number of words giving each
+    /// response. The latter divided by the number of words is the expected number of words left.
+    pub fn worst_case(&self, guess: &Word) -> (usize, usize) {
+        let mut buckets = vec![0usize; pattern_count(guess.len())];
+        for word in &self.words {
+            buckets[pattern_code(word, guess)] += 1;
+        }
+        let largest = buckets.iter().copied().max().unwrap_or(0);
+        (largest, buckets.iter().map(|count| count * count).sum())
+    }
+
+    /// returns the word from `guesses` whose most common response leaves the fewest words, with
+    /// the fewest words left on average breaking ties. Words that could still be the answer win
+    /// the remaining ties.
+    pub fn get_minimax_word(&self, guesses: &WordCollection) -> Option<Word> {
+        match self.words.len() {
+            0 => return None,
+            1 | 2 => return Some(self.words[0]),
+            _ => {}
+        }
+        let mut best_word = self.words[0];
+        let mut best_score = self.worst_case(&best_word);
+        for word in self.words.iter().chain(guesses.words.iter()) {
+            let score = self.worst_case(word);
+            if score < best_score {
+                best_word = *word;
+                best_score = score;
+            }
+        }
+        Some(best_word)
+    }
+
+    /// returns a new collection with only those words that satisfy the current state.
+    /// This is called after the state has been updated by the wordle response.
+    pub fn filter(&self, state: &WordleState) -> WordCollection {
+        let mut words = Vec::new();
+        for word in &self.words {
+            if state.is_allowed(word) {
+                words.push(*word);
+            }
+        }
+        WordCollection {
+            words,
+            word_len: self.word_len
+        }
+    }
+
+    /// the words of this collection followed by the words of `other` that are not in it.
+    pub fn union(&self, other: &WordCollection) -> WordCollection {
+        let mut seen: HashSet<Word> = self.words.iter().copied().collect();
+        let mut words = self.words.clone();
+        words.extend(other.words.iter().filter(|w| seen.insert(**w)));
+        WordCollection {
+            words,
+            word_len: self.word_len
+        }
+    }
+
+    pub fn remove(&mut self, word: &Word) {
+        self.words.retain(|x| x != word);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "bundled")]
+    #[test]
+    fn test_bundled() {
+        use super::*;
+        let words = WordCollection::bundled(5);
+        assert_eq!(words.len(), 213);
+        assert_eq!(BUNDLED_WORDS.lines().count(), words.len());
+        assert!(words.words().iter().all(|word| word.len() == 5));
+    }
+}
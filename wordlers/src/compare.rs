@@ -0,0 +1,164 @@
+use crate::error::Error;
+use crate::feedback::{Feedback, Tile};
+use crate::word::{Word, MAX_WORD_LEN};
+
+/// compares the guess `word` against the target `given` and returns the wordle response. Fails
+/// when `word` is too long for a response.
+pub fn wordle_compare(given: &str, word: &str) -> Result<Feedback, Error> {
+    let given: Vec<char> = given.chars().collect();
+    let word: Vec<char> = word.chars().collect();
+    if word.len() > MAX_WORD_LEN {
+        return Err(Error::Invalid(format!("a guess has at most {} letters", MAX_WORD_LEN)));
+    }
+    let mut tiles = vec![Tile::Gray; word.len()];
+    let mut visited = vec![false; given.len()];
+    for (i, c) in word.iter().enumerate() {
+        if given.get(i) == Some(c) {
+            tiles[i] = Tile::Green;
+            visited[i] = true;
+        }
+    }
+    for (i, x) in word.iter().enumerate() {
+        if tiles[i] == Tile::Green {
+            continue;
+        }
+        let found = given.iter().enumerate()
+            .position(|(j, c)| !visited[j] && c == x);
+        if let Some(j) = found {
+            tiles[i] = Tile::Yellow;
+            visited[j] = true;
+        }
+    }
+    Ok(Feedback::new(&tiles))
+}
+
+/// the number of distinct wordle responses for a word with `len` letters.
+pub const fn pattern_count(len: usize) -> usize {
+    3usize.pow(len as u32)
+}
+
+/// the wordle response for guessing `word` when the target is `given`, encoded as a base 3
+/// number (N = 0, Y = 1, G = 2) with the first letter as the most significant digit. This is the
+/// allocation free counterpart of [`wordle_compare`], and like it takes words of any lengths: the
+/// code has a digit for each letter of `word`.
+pub fn pattern_code(given: &Word, word: &Word) -> usize {
+    let given = given.letters();
+    let word = word.letters();
+    let mut digits = [0; MAX_WORD_LEN];
+    let mut visited = [false; MAX_WORD_LEN];
+    for i in 0..word.len() {
+        if given.get(i) == Some(&word[i]) {
+            digits[i] = 2;
+            visited[i] = true;
+        }
+    }
+    for i in 0..word.len() {
+        if digits[i] == 2 {
+            continue;
+        }
+        if let Some(j) = (0..given.len()).find(|&j| !visited[j] && given[j] == word[i]) {
+            digits[i] = 1;
+            visited[j] = true;
+        }
+    }
+    digits[..word.len()].iter().fold(0, |code, d| code * 3 + d)
+}
+
+/// whether `word` uses every hint that `response` gave to `guess`, as hard mode requires: green
+/// letters stay where they are and yellow letters are played again.
+pub fn uses_hints(word: &Word, guess: &Word, response: &Feedback) -> bool {
+    let (word, guess) = (word.letters(), guess.letters());
+    let mut needed = [0u8; 26];
+    for (i, tile) in response.tiles().iter().enumerate() {
+        match tile {
+            Tile::Green if word.get(i) != guess.get(i) => return false,
+            Tile::Green | Tile::Yellow => needed[(guess[i] - b'a') as usize] += 1,
+            Tile::Gray => {}
+        }
+    }
+    let mut counts = [0u8; 26];
+    for b in word {
+        counts[(b - b'a') as usize] += 1;
+    }
+    needed.iter().zip(counts).all(|(needed, count)| *needed <= count)
+}
+
+/// compares the guess `word` against the target `given` for Cows and Bulls and returns the
+/// number of bulls and cows.
+pub fn cows_and_bulls(given: &str, word: &str) -> (u32, u32) {
+    count_cows_and_bulls(given.as_bytes(), word.as_bytes())
+}
+
+/// [`cows_and_bulls`] for words that have already been parsed.
+pub fn word_cows_and_bulls(given: &Word, word: &Word) -> (u32, u32) {
+    count_cows_and_bulls(given.letters(), word.letters())
+}
+
+/// a letter of the guess is a bull when it is in the same place in the target, and a cow when it
+/// is anywhere else in the target.
+fn count_cows_and_bulls(given: &[u8], word: &[u8]) -> (u32, u32) {
+    let mut cows = 0;
+    let mut bulls = 0;
+    for (i, r) in word.iter().enumerate() {
+        if given.get(i) == Some(r) {
+            bulls += 1;
+        }
+        else if given.iter().enumerate().any(|(j, c)| i != j && c == r) {
+            cows += 1;
+        }
+    }
+    (bulls, cows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wordle_compare() {
+        assert_eq!(wordle_compare("hello", "henlo").unwrap().to_string(), "GGNGG");
+        assert_eq!(wordle_compare("hello", "hello").unwrap().to_string(), "GGGGG");
+        assert_eq!(wordle_compare("hello", "olleh").unwrap().to_string(), "YYGYY");
+        assert_eq!(wordle_compare("hello", "ollen").unwrap().to_string(), "YYGYN");
+        assert_eq!(wordle_compare("hello", "lllen").unwrap().to_string(), "YNGYN");
+        assert_eq!(wordle_compare("hello", "lllle").unwrap().to_string(), "NNGGY");
+    }
+
+    #[test]
+    fn test_uses_hints() {
+        let word = |w: &str| Word::new(w).unwrap();
+        let response = wordle_compare("brine", "crane").unwrap();
+        assert!(uses_hints(&word("prone"), &word("crane"), &response));
+        assert!(uses_hints(&word("brine"), &word("crane"), &response));
+        // the R is not kept.
+        assert!(!uses_hints(&word("spine"), &word("crane"), &response));
+        // the R and the N move.
+        assert!(!uses_hints(&word("rinse"), &word("crane"), &response));
+    }
+
+    #[test]
+    fn test_pattern_code() {
+        for (given, word) in [("hello", "henlo"), ("hello", "olleh"), ("hello", "lllle"), ("speed", "geese")] {
+            let code = pattern_code(&Word::new(given).unwrap(), &Word::new(word).unwrap());
+            assert_eq!(code, wordle_compare(given, word).unwrap().code());
+        }
+    }
+
+    #[test]
+    fn test_other_lengths() {
+        assert_eq!(wordle_compare("moon", "nook").unwrap().to_string(), "YGGN");
+        assert_eq!(wordle_compare("banana", "ananas").unwrap().to_string(), "YYYYYN");
+        assert_eq!(pattern_code(&Word::new("moon").unwrap(), &Word::new("nook").unwrap()), 27 + 2 * 9 + 2 * 3);
+        assert_eq!(pattern_count(4), 81);
+        let (long, short) = (Word::new("hello").unwrap(), Word::new("hel").unwrap());
+        assert_eq!(pattern_code(&short, &long), wordle_compare("hel", "hello").unwrap().code());
+        assert_eq!(pattern_code(&long, &short), wordle_compare("hello", "hel").unwrap().code());
+        assert!(wordle_compare("hello", "hellohellohello").is_err());
+    }
+
+    #[test]
+    fn test_cows_and_bulls() {
+        assert_eq!(cows_and_bulls("hello", "hello"), (5, 0));
+        assert_eq!(cows_and_bulls("hello", "world"), (1, 1));
+    }
+}
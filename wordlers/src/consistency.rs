@@ -0,0 +1,85 @@
+use crate::collection::WordCollection;
+use crate::feedback::{Feedback, Tile};
+use crate::state::WordleState;
+use crate::word::Word;
+
+/// why no answer fits a history of guesses and responses, and how to get out of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inconsistency {
+    /// the position in the history of the step after which no answer was left.
+    pub step: usize,
+    /// whether the responses up to that step contradict each other, whatever the word list. When
+    /// false, they only rule out every word of the list.
+    pub contradictory: bool,
+    /// responses that differ from the history in a single tile and leave some answers: the
+    /// position of the step, the corrected response and the number of answers left.
+    pub fixes: Vec<(usize, Feedback, usize)>,
+}
+
+/// the state and the answers left after each step of the history, up to the first step that is
+/// not as long as the answers.
+fn replay(answers: &WordCollection, history: &[(Word, Feedback)]) -> Vec<(WordleState, usize)> {
+    let mut state = WordleState::new(answers.word_len());
+    let mut candidates = answers.clone();
+    history.iter()
+        .map_while(|(guess, response)| {
+            state.update(guess, response).ok()?;
+            candidates = candidates.filter(&state);
+            Some((state.clone(), candidates.len()))
+        })
+        .collect()
+}
+
+/// finds the step of the `history` after which none of the `answers` fits any more, or None
+/// when some still do. The likely typos come with it: every single tile change in the history
+/// that leaves some answers, the earliest steps first.
+pub fn find_inconsistency(answers: &WordCollection, history: &[(Word, Feedback)]) -> Option<Inconsistency> {
+    let steps = replay(answers, history);
+    let step = steps.iter().position(|(_, count)| *count == 0)?;
+    let contradictory = !steps[step].0.is_satisfiable();
+    let mut fixes = Vec::new();
+    let mut edited = history.to_vec();
+    for (i, (_, response)) in history.iter().enumerate() {
+        for position in 0..response.len() {
+            for tile in [Tile::Green, Tile::Yellow, Tile::Gray] {
+                if tile == response.tiles()[position] {
+                    continue;
+                }
+                let mut tiles = response.tiles().to_vec();
+                tiles[position] = tile;
+                let fixed = Feedback::new(&tiles);
+                if fixed.is_solved() {
+                    continue;
+                }
+                edited[i].1 = fixed;
+                let left = replay(answers, &edited).last().map_or(answers.len(), |(_, count)| *count);
+                if left > 0 {
+                    fixes.push((i, fixed, left));
+                }
+            }
+        }
+        edited[i].1 = *response;
+    }
+    Some(Inconsistency { step, contradictory, fixes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compare::wordle_compare;
+
+    #[test]
+    fn test_find_inconsistency() {
+        let answers = WordCollection::from_lines(["crane", "build", "motif", "shine", "spine"], 5);
+        let step = |guess: &str, response: &str| (Word::new(guess).unwrap(), response.parse().unwrap());
+        let fine = [(Word::new("crane").unwrap(), wordle_compare("shine", "crane").unwrap())];
+        assert_eq!(find_inconsistency(&answers, &fine), None);
+
+        // the target is shine, but the S of spine was reported gray after being green.
+        let history = [step("slate", "GNNNG"), step("spine", "NNGGG")];
+        let inconsistency = find_inconsistency(&answers, &history).unwrap();
+        assert_eq!(inconsistency.step, 1);
+        assert!(inconsistency.contradictory);
+        assert!(inconsistency.fixes.contains(&(1, "GNGGG".parse().unwrap(), 1)));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::collection::WordCollection;
+use crate::compare::word_cows_and_bulls;
+use crate::word::Word;
+
+/// solves Cows and Bulls. It keeps the words consistent with every reply so far and picks the
+/// guess that leaves the fewest of them on average.
+#[derive(Debug, Clone)]
+pub struct CowsAndBullsSolver {
+    /// the full list of possible targets the solver started with.
+    answers: WordCollection,
+    /// the words accepted as guesses.
+    guesses: WordCollection,
+    /// the targets consistent with every reply seen so far.
+    candidates: WordCollection,
+}
+
+impl CowsAndBullsSolver {
+    pub fn new(answers: WordCollection) -> CowsAndBullsSolver {
+        CowsAndBullsSolver {
+            candidates: answers.clone(),
+            guesses: answers.clone(),
+            answers,
+        }
+    }
+
+    /// allows the solver to guess any of the given words, on top of the answers.
+    pub fn with_guesses(mut self, guesses: WordCollection) -> CowsAndBullsSolver {
+        self.guesses = guesses;
+        self
+    }
+
+    /// the targets that are still possible.
+    pub fn candidates(&self) -> &WordCollection {
+        &self.candidates
+    }
+
+    /// the number of candidates we expect to be left after playing `guess`.
+    pub fn expected_remaining(&self, guess: &Word) -> f64 {
+        let len = self.candidates.word_len() + 1;
+        let mut buckets = vec![0usize; len * len];
+        for word in self.candidates.words() {
+            let (bulls, cows) = word_cows_and_bulls(word, guess);
+            buckets[bulls as usize * len + cows as usize] += 1;
+        }
+        let squares: usize = buckets.iter().map(|count| count * count).sum();
+        squares as f64 / self.candidates.len() as f64
+    }
+
+    /// the next word to guess, or None when no word is consistent with the replies. Candidates
+    /// win ties, since they might be the target.
+    pub fn suggest(&self) -> Option<Word> {
+        match self.candidates.len() {
+            0 => return None,
+            1 | 2 => return Some(self.candidates.words()[0]),
+            _ => {}
+        }
+        let mut best_word = self.candidates.words()[0];
+        let mut best_score = self.expected_remaining(&best_word);
+        for word in self.candidates.words().iter().chain(self.guesses.words().iter()) {
+            let score = self.expected_remaining(word);
+            if score < best_score - 1e-9 {
+                best_word = *word;
+                best_score = score;
+            }
+        }
+        Some(best_word)
+    }
+
+    /// keeps only the candidates that would have given the same number of bulls and cows.
+    pub fn feedback(&mut self, guess: &Word, bulls: u32, cows: u32) {
+        let words = self.candidates.words().iter()
+            .filter(|word| word_cows_and_bulls(word, guess) == (bulls, cows))
+            .copied()
+            .collect();
+        self.candidates = WordCollection::from_words(words);
+    }
+
+    /// forgets every reply and starts over with the full answer list.
+    pub fn reset(&mut self) {
+        self.candidates = self.answers.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cows_and_bulls_solver() {
+        let words = WordCollection::from_lines(
+            ["crane", "slate", "brine", "shine", "prone", "adieu", "pylon", "crows", "fight"], 5);
+        for target in words.words() {
+            let mut solver = CowsAndBullsSolver::new(words.clone());
+            let found = (0..9).any(|_| {
+                let guess = solver.suggest().unwrap();
+                let (bulls, cows) = word_cows_and_bulls(target, &guess);
+                solver.feedback(&guess, bulls, cows);
+                guess == *target
+            });
+            assert!(found, "did not find {}", target);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::feedback::ParseFeedbackError;
+use crate::share::ParseShareError;
+
+/// what can go wrong when loading word lists, reading input or saving a game.
+#[derive(Debug)]
+pub enum Error {
+    /// reading or writing failed, on the file at `path` if there is one, or else on the terminal.
+    Io { path: Option<PathBuf>, source: io::Error },
+    /// some text is not what it should be: a response, a shared result, a line of a session.
+    Parse(String),
+    /// the input makes sense but cannot be used, like a word that is too long.
+    Invalid(String),
+}
+
+impl Error {
+    /// an error on the file at `path`.
+    pub fn file(path: impl Into<PathBuf>, source: io::Error) -> Error {
+        Error::Io { path: Some(path.into()), source }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path: Some(path), source } => write!(f, "{}: {}", path.display(), source),
+            Error::Io { path: None, source } => write!(f, "{}", source),
+            Error::Parse(message) | Error::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Error {
+        Error::Io { path: None, source }
+    }
+}
+
+impl From<ParseFeedbackError> for Error {
+    fn from(e: ParseFeedbackError) -> Error {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<ParseShareError> for Error {
+    fn from(e: ParseShareError) -> Error {
+        Error::Parse(e.to_string())
+    }
+}
@@ -0,0 +1,214 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::word::MAX_WORD_LEN;
+
+/// the colour wordle gives a single letter of a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Tile {
+    /// the letter is not in the target (or not as many times as it is in the guess).
+    #[default]
+    Gray,
+    /// the letter is in the target, somewhere else.
+    Yellow,
+    /// the letter is in the target at this position.
+    Green,
+}
+
+impl Tile {
+    /// the tile for a letter of a typed response: G, Y, and N (nothing) or B (black) for gray, in
+    /// either case. The squares of [`Tile::from_square`] work too.
+    pub fn from_char(c: char) -> Option<Tile> {
+        match c {
+            'G' | 'g' => Some(Tile::Green),
+            'Y' | 'y' => Some(Tile::Yellow),
+            'N' | 'n' | 'B' | 'b' => Some(Tile::Gray),
+            _ => Tile::from_square(c),
+        }
+    }
+
+    /// the tile for a square emoji, as wordle shares it: black or white for gray, and the orange
+    /// (green) and blue (yellow) squares of the high contrast theme.
+    pub fn from_square(c: char) -> Option<Tile> {
+        match c {
+            '🟩' | '🟧' => Some(Tile::Green),
+            '🟨' | '🟦' => Some(Tile::Yellow),
+            '⬛' | '⬜' => Some(Tile::Gray),
+            _ => None,
+        }
+    }
+
+    /// the letter used for the tile in G/Y/N responses.
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Green => 'G',
+            Tile::Yellow => 'Y',
+            Tile::Gray => 'N',
+        }
+    }
+
+    /// the square wordle shares the tile as.
+    pub fn to_emoji(self) -> char {
+        match self {
+            Tile::Green => '🟩',
+            Tile::Yellow => '🟨',
+            Tile::Gray => '⬛',
+        }
+    }
+
+    /// the digit of the tile in [`Feedback::code`].
+    fn digit(self) -> usize {
+        self as usize
+    }
+}
+
+/// the response wordle gives for a guess: one [`Tile`] per letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Feedback {
+    /// the tiles. Only the first `len` are used, the rest are gray.
+    tiles: [Tile; MAX_WORD_LEN],
+    /// the number of letters in the guess.
+    len: u8,
+}
+
+impl Feedback {
+    pub fn new(tiles: &[Tile]) -> Feedback {
+        assert!(tiles.len() <= MAX_WORD_LEN, "a response has at most {} tiles", MAX_WORD_LEN);
+        let mut all = [Tile::Gray; MAX_WORD_LEN];
+        all[..tiles.len()].copy_from_slice(tiles);
+        Feedback {
+            tiles: all,
+            len: tiles.len() as u8,
+        }
+    }
+
+    /// the response to guessing the target itself.
+    pub fn solved(len: usize) -> Feedback {
+        Feedback::new(&vec![Tile::Green; len])
+    }
+
+    /// the response decoded from its [`Feedback::code`].
+    pub fn from_code(mut code: usize, len: usize) -> Feedback {
+        let mut tiles = vec![Tile::Gray; len];
+        for tile in tiles.iter_mut().rev() {
+            *tile = match code % 3 {
+                2 => Tile::Green,
+                1 => Tile::Yellow,
+                _ => Tile::Gray,
+            };
+            code /= 3;
+        }
+        Feedback::new(&tiles)
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles[..self.len()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// true when every tile is green.
+    pub fn is_solved(&self) -> bool {
+        !self.is_empty() && self.tiles().iter().all(|t| *t == Tile::Green)
+    }
+
+    /// the number of tiles that are green or yellow.
+    pub fn known_count(&self) -> usize {
+        self.tiles().iter().filter(|t| **t != Tile::Gray).count()
+    }
+
+    /// the response as a base 3 number (gray = 0, yellow = 1, green = 2) with the first tile as
+    /// the most significant digit. This is what [`pattern_code`](crate::pattern_code) computes.
+    pub fn code(&self) -> usize {
+        self.tiles().iter().fold(0, |code, tile| code * 3 + tile.digit())
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for tile in self.tiles() {
+            write!(f, "{}", tile.to_char())?;
+        }
+        Ok(())
+    }
+}
+
+/// why a response could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFeedbackError {
+    /// the character that is not a tile.
+    pub invalid: char,
+}
+
+impl fmt::Display for ParseFeedbackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not one of G, Y or N (or B)", self.invalid)
+    }
+}
+
+impl Error for ParseFeedbackError {}
+
+impl Feedback {
+    /// reads a row of a shared result, made of square emoji only (see [`Tile::from_square`]).
+    /// Whitespace and emoji variation selectors are ignored.
+    pub fn from_squares(s: &str) -> Result<Feedback, ParseFeedbackError> {
+        Feedback::parse_with(s, Tile::from_square)
+    }
+
+    fn parse_with(s: &str, tile: impl Fn(char) -> Option<Tile>) -> Result<Feedback, ParseFeedbackError> {
+        let mut tiles = Vec::new();
+        for c in s.chars().filter(|c| !c.is_whitespace() && *c != '\u{fe0f}') {
+            let tile = tile(c).ok_or(ParseFeedbackError { invalid: c })?;
+            if tiles.len() == MAX_WORD_LEN {
+                return Err(ParseFeedbackError { invalid: c });
+            }
+            tiles.push(tile);
+        }
+        Ok(Feedback::new(&tiles))
+    }
+}
+
+impl FromStr for Feedback {
+    type Err = ParseFeedbackError;
+
+    /// reads a response written with G/Y/N (or B for gray) letters or with emoji squares.
+    /// Whitespace and emoji variation selectors are ignored.
+    fn from_str(s: &str) -> Result<Feedback, ParseFeedbackError> {
+        Feedback::parse_with(s, Tile::from_char)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let expected = Feedback::new(&[Tile::Green, Tile::Yellow, Tile::Gray, Tile::Gray, Tile::Green]);
+        assert_eq!("GYNNG".parse(), Ok(expected));
+        assert_eq!("gybbg".parse(), Ok(expected));
+        assert_eq!("🟩🟨⬛⬜🟩".parse(), Ok(expected));
+        assert_eq!("🟧🟦⬛⬛🟧".parse(), Ok(expected));
+        assert_eq!("GYXNG".parse::<Feedback>(), Err(ParseFeedbackError { invalid: 'X' }));
+        assert_eq!("GYWNG".parse::<Feedback>(), Err(ParseFeedbackError { invalid: 'W' }));
+        assert_eq!(Feedback::from_squares("🟩🟨⬛⬜🟩"), Ok(expected));
+        assert_eq!(Feedback::from_squares("gg"), Err(ParseFeedbackError { invalid: 'g' }));
+        assert_eq!(expected.to_string(), "GYNNG");
+    }
+
+    #[test]
+    fn test_code() {
+        let feedback: Feedback = "YGGN".parse().unwrap();
+        assert_eq!(feedback.code(), 27 + 2 * 9 + 2 * 3);
+        assert_eq!(Feedback::from_code(feedback.code(), 4), feedback);
+        assert!(Feedback::solved(5).is_solved());
+        assert!(!feedback.is_solved());
+    }
+}
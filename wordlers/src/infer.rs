@@ -0,0 +1,63 @@
+use crate::collection::WordCollection;
+use crate::compare::{pattern_code, pattern_count};
+use crate::share::ShareGrid;
+use crate::word::Word;
+
+/// works out which answers could have produced every row of the shared `grids`, all for the same
+/// puzzle. An answer survives when each row is the response to at least one of the `guesses`.
+///
+/// The survivors come with a score, highest first: the log2 of the chance that a random guess
+/// gives each row, summed over the distinct rows. An answer that makes the rows common responses
+/// explains the grids better than one that needs a rare guess for each of them.
+pub fn infer_answers(grids: &[ShareGrid], answers: &WordCollection, guesses: &WordCollection) -> Vec<(Word, f64)> {
+    let mut rows: Vec<usize> = grids.iter()
+        .flat_map(|grid| grid.rows.iter())
+        .filter(|row| row.len() == answers.word_len())
+        .map(|row| row.code())
+        .collect();
+    rows.sort();
+    rows.dedup();
+    // guesses of another length cannot have made the rows.
+    let guesses: Vec<&Word> = guesses.words().iter().filter(|guess| guess.len() == answers.word_len()).collect();
+    let mut survivors = Vec::new();
+    let mut ways = vec![0usize; pattern_count(answers.word_len())];
+    for answer in answers.words() {
+        ways.iter_mut().for_each(|count| *count = 0);
+        for guess in &guesses {
+            ways[pattern_code(answer, guess)] += 1;
+        }
+        if rows.iter().all(|row| ways[*row] > 0) {
+            let total = guesses.len() as f64;
+            let score: f64 = rows.iter().map(|row| (ways[*row] as f64 / total).log2()).sum();
+            survivors.push((*answer, score));
+        }
+    }
+    survivors.sort_by(|(w1, s1), (w2, s2)| s2.total_cmp(s1).then_with(|| w1.cmp(w2)));
+    survivors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compare::wordle_compare;
+    use crate::feedback::Feedback;
+
+    #[test]
+    fn test_infer_answers() {
+        let words = WordCollection::from_lines(
+            ["crane", "slate", "brine", "shine", "prone", "adieu", "pylon", "crows", "fight"], 5);
+        let grid = |guesses: &[&str]| ShareGrid {
+            title: None,
+            score: Some(guesses.len()),
+            hard_mode: false,
+            rows: guesses.iter().map(|guess| wordle_compare("shine", guess).unwrap()).collect(),
+        };
+        let grids = [grid(&["crane", "brine", "shine"]), grid(&["fight", "shine"]), grid(&["adieu", "shine"])];
+        let survivors = infer_answers(&grids, &words, &words);
+        assert_eq!(survivors[0].0, Word::new("shine").unwrap());
+        assert!(survivors.iter().all(|(word, _)| word.to_string() != "pylon"));
+
+        let impossible = ShareGrid { rows: vec![Feedback::solved(5), "GGGGY".parse().unwrap()], ..grid(&[]) };
+        assert!(infer_answers(&[impossible], &words, &words).is_empty());
+    }
+}
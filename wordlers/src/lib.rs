@@ -0,0 +1,53 @@
+//! solvers for wordle and friends.
+//!
+//! The [`Solver`] keeps track of the responses seen so far and suggests the next guess. The
+//! building blocks it is made of ([`Word`], [`WordleState`], [`WordCollection`] and
+//! [`wordle_compare`]) are exposed as well, for those who want to roll their own.
+
+extern crate rand;
+
+mod absurdle;
+mod bench;
+mod charset;
+mod collection;
+mod compare;
+mod consistency;
+mod cows;
+mod error;
+mod feedback;
+mod infer;
+mod matrix;
+mod multi;
+mod opening;
+mod picture;
+mod session;
+mod shape;
+mod share;
+mod solver;
+mod state;
+mod strategy;
+mod tree;
+mod word;
+
+pub use absurdle::Absurdle;
+pub use bench::{play_game, play_games, BenchReport, GIVE_UP_AFTER, MAX_GUESSES};
+pub use charset::CharSet;
+pub use collection::WordCollection;
+pub use compare::{cows_and_bulls, pattern_code, pattern_count, uses_hints, word_cows_and_bulls, wordle_compare};
+pub use consistency::{find_inconsistency, Inconsistency};
+pub use cows::CowsAndBullsSolver;
+pub use error::Error;
+pub use feedback::{Feedback, ParseFeedbackError, Tile};
+pub use infer::infer_answers;
+pub use matrix::PatternMatrix;
+pub use multi::MultiSolver;
+pub use opening::{Opening, SOFT_OPENERS};
+pub use picture::{nearest_patterns, Picture};
+pub use session::Session;
+pub use shape::ShapeSearch;
+pub use share::{ParseShareError, ShareGrid};
+pub use solver::Solver;
+pub use state::WordleState;
+pub use strategy::Strategy;
+pub use tree::{DecisionTree, Objective, TreeSearch};
+pub use word::{Word, MAX_WORD_LEN};
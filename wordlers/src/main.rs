@@ -1,711 +1,847 @@
 extern crate colored;
-extern crate rand;
+extern crate wordlers;
 
-use std::fmt;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use colored::*;
-use rand::prelude::*;
-
-/// a set for alphabetical characters. Considering the nature of the problem, we can use an
-/// unsigned integer to represent the set.
-struct CharSet{
-    /// the set of characters
-    chars: u32,
-}
-
-impl CharSet {
-    fn new() -> CharSet {
-        CharSet { chars: 0 }
-    }
-
-    fn new_full() -> CharSet {
-        CharSet { chars: 0xFFFFFFFF }
-    }
-
-    fn add(&mut self, c: char) {
-        self.chars |= 1 << (c as u32 - 'a' as u32);
-    }
-
-    fn contains(&self, c: char) -> bool {
-        (self.chars & (1 << (c as u32 - 'a' as u32))) != 0
-    }
-
-    fn remove(&mut self, c: char) {
-        self.chars &= !(1 << (c as u32 - 'a' as u32));
-    }
-
-    fn is_subset_of(&self, other: &CharSet) -> bool {
-        (self.chars & other.chars) == self.chars
-    }
-
-    fn remove_others(&mut self, c: char) {
-        self.chars = 1 << (c as u32 - 'a' as u32);
-    }
+mod cli;
 
-    /*fn cardinality(&self) -> usize {
-        self.chars.count_ones() as usize
-    }*/
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use clap::Parser;
+use colored::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::json;
+use cli::{Cli, Command, Format, Limit, ListArgs, SessionArgs};
+use wordlers::{cows_and_bulls, find_inconsistency, infer_answers, nearest_patterns, play_games, uses_hints, wordle_compare, Absurdle, CowsAndBullsSolver, DecisionTree, Error, Feedback, MultiSolver, Objective, Opening, PatternMatrix, Picture, Session, ShapeSearch, ShareGrid, Solver, Strategy, Tile, TreeSearch, Word, WordCollection, GIVE_UP_AFTER, MAX_GUESSES};
+
+/// the file name of Knuth's list of five letter words.
+const SGB_WORDS: &str = "sgb-words.txt";
+
+/// where Knuth's list can be downloaded from.
+#[cfg(all(feature = "download", not(feature = "bundled")))]
+const SGB_URL: &str = "https://www-cs-faculty.stanford.edu/~knuth/sgb-words.txt";
+
+/// where a word list comes from.
+#[derive(Debug, Clone)]
+enum WordSource {
+    File(PathBuf),
+    /// the list compiled into the program.
+    #[cfg(feature = "bundled")]
+    Bundled,
 }
 
-impl fmt::Display for CharSet {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut chars = vec![];
-        for i in 0..26 {
-            if self.chars & (1 << i) != 0 {
-                chars.push((i + 'a' as u8)  as char);
-            }
+impl WordSource {
+    fn load(&self, word_len: usize) -> Result<WordCollection, Error> {
+        match self {
+            WordSource::File(path) => WordCollection::new(path, word_len),
+            #[cfg(feature = "bundled")]
+            WordSource::Bundled => Ok(WordCollection::bundled(word_len)),
         }
-        write!(f, "<{}>", chars.iter().collect::<String>())
     }
 }
 
-/// the word consists of five characters.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Word {
-    /// the first character
-    c0 : char,
-    /// the second character
-    c1 : char,
-    /// the third character
-    c2 : char,
-    /// the fourth character
-    c3 : char,
-    /// the fifth character
-    c4 : char
+/// the directories searched for word lists: `$XDG_DATA_HOME` (or `~/.local/share`), then
+/// `$XDG_DATA_DIRS` (or `/usr/local/share` and `/usr/share`), each with a `wordlers` directory.
+fn data_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")));
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    home.into_iter()
+        .chain(system.split(':').filter(|dir| !dir.is_empty()).map(PathBuf::from))
+        .map(|dir| dir.join("wordlers"))
+        .collect()
 }
 
-impl Word {
-    fn new(str : &str) -> Word {
-        Word {
-            c0 : str.chars().nth(0).unwrap(),
-            c1 : str.chars().nth(1).unwrap(),
-            c2 : str.chars().nth(2).unwrap(),
-            c3 : str.chars().nth(3).unwrap(),
-            c4 : str.chars().nth(4).unwrap()
-        }
-    }
-
-    fn to_string(&self) -> String {
-        format!("{}{}{}{}{}", self.c0, self.c1, self.c2, self.c3, self.c4)
+/// the word list to use when none is given: `$WORDLERS_WORDS`, the sgb list in the data
+/// directories, the bundled sample and, as a last resort, the sgb list downloaded to the first data
+/// directory.
+fn default_words() -> Result<WordSource, Error> {
+    if let Some(path) = std::env::var_os("WORDLERS_WORDS") {
+        return Ok(WordSource::File(PathBuf::from(path)));
     }
-
-    fn has_repeated_chars(&self) -> bool {
-        self.c0 == self.c1 || self.c0 == self.c2 || self.c0 == self.c3 || self.c0 == self.c4 ||
-        self.c1 == self.c2 || self.c1 == self.c3 || self.c1 == self.c4 ||
-        self.c2 == self.c3 || self.c2 == self.c4 ||
-        self.c3 == self.c4
+    let dirs = data_dirs();
+    if let Some(path) = dirs.iter().map(|dir| dir.join(SGB_WORDS)).find(|path| path.is_file()) {
+        return Ok(WordSource::File(path));
     }
-
-    fn score(&self, scores: &Vec<usize>) -> usize {
-       let raw_score =  scores[self.c0 as usize - 'a' as usize] +
-           scores[self.c1 as usize - 'a' as usize] + 
-           scores[self.c2 as usize - 'a' as usize] +
-           scores[self.c3 as usize - 'a' as usize] +
-           scores[self.c4 as usize - 'a' as usize];
-         if self.has_repeated_chars() {
-            raw_score - 20
-         } else {
-            raw_score
-         }
+    #[cfg(feature = "bundled")]
+    return Ok(WordSource::Bundled);
+    #[cfg(not(feature = "bundled"))]
+    match dirs.first() {
+        Some(dir) => download_words(&dir.join(SGB_WORDS)).map(|_| WordSource::File(dir.join(SGB_WORDS))),
+        None => Err(Error::Invalid("there is no word list: pass one with --words or set WORDLERS_WORDS".to_string())),
     }
+}
 
-    fn to_char_set(&self) -> CharSet {
-        let mut char_set = CharSet::new();
-        char_set.add(self.c0);
-        char_set.add(self.c1);
-        char_set.add(self.c2);
-        char_set.add(self.c3);
-        char_set.add(self.c4);
-        char_set
-    }
+/// downloads Knuth's list to `path`.
+#[cfg(all(feature = "download", not(feature = "bundled")))]
+fn download_words(path: &Path) -> Result<(), Error> {
+    eprintln!("downloading the word list to {}", path.display());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| Error::file(dir, e))?;
+    }
+    let mut response = reqwest::blocking::get(SGB_URL)
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| {
+            let message = format!("cannot download {}: {}\npass a word list with --words or set WORDLERS_WORDS", SGB_URL, e);
+            Error::from(io::Error::other(message))
+        })?;
+    // written aside first, so that a failed download does not leave half a list behind.
+    let partial = path.with_extension("part");
+    let mut file = std::fs::File::create(&partial).map_err(|e| Error::file(&partial, e))?;
+    io::copy(&mut response, &mut file).map_err(|e| Error::file(&partial, e))?;
+    std::fs::rename(&partial, path).map_err(|e| Error::file(path, e))
 }
 
-impl fmt::Display for Word {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
+/// without the download feature, the list has to be put in place by hand.
+#[cfg(all(not(feature = "download"), not(feature = "bundled")))]
+fn download_words(path: &Path) -> Result<(), Error> {
+    Err(Error::Invalid(format!("there is no word list: pass one with --words, set WORDLERS_WORDS or put {} in {}",
+                               SGB_WORDS, path.parent().unwrap_or(path).display())))
 }
 
-/// The constraints on the words. Each constraint is represented as a set of characters.
-struct WordleState {
-    /// the characters allowed in the first position.
-    c0 : CharSet,
-    /// the characters allowed in the second position.
-    c1 : CharSet,
-    /// the characters allowed in the third position.
-    c2 : CharSet,
-    /// the characters allowed in the fourth position.
-    c3 : CharSet,
-    /// the characters allowed in the fifth position.
-    c4 : CharSet,
-    /// the characters known to be in the target word.
-    in_target: CharSet
+/// the options given on the command line.
+#[derive(Clone)]
+struct Config {
+    /// how the solver picks its guesses.
+    strategy: Strategy,
+    /// the number of letters in each word.
+    word_len: usize,
+    /// the words accepted as guesses.
+    words: WordSource,
+    /// the words that can be the answer.
+    answers: WordSource,
+    /// where the guesses and responses of the solver are recorded.
+    session_file: PathBuf,
+    /// whether to carry on with the game in the session file rather than start a new one.
+    resume: bool,
+    /// the decision tree the solver follows, if any.
+    tree: Option<PathBuf>,
 }
 
-impl WordleState {
-    fn new() -> WordleState {
-        WordleState {
-            c0: CharSet::new_full(),
-            c1: CharSet::new_full(),
-            c2: CharSet::new_full(),
-            c3: CharSet::new_full(),
-            c4: CharSet::new_full(),
-            in_target: CharSet::new()
-        }
+impl Config {
+    fn answers(&self) -> Result<WordCollection, Error> {
+        self.answers.load(self.word_len)
     }
 
-    fn is_allowed(&self, word: &Word) -> bool {
-        self.in_target.is_subset_of(&word.to_char_set()) &&
-            self.c0.contains(word.c0) && 
-            self.c1.contains(word.c1) && 
-            self.c2.contains(word.c2) &&
-            self.c3.contains(word.c3) &&
-            self.c4.contains(word.c4)
-    }
-
-    /// gets called when we encounter an N letter. That is, the letter in the guess that is neither green
-    /// nor yellow.
-    fn remove_letter(&mut self, c: char) {
-        self.c0.remove(c);
-        self.c1.remove(c);
-        self.c2.remove(c);
-        self.c3.remove(c);
-        self.c4.remove(c);
+    /// the allowed guesses. The answers are always allowed, even when the guess list leaves them
+    /// out.
+    fn guesses(&self) -> Result<WordCollection, Error> {
+        Ok(self.answers()?.union(&self.words.load(self.word_len)?))
     }
 
-
-    /// once we got a new word and the response from wordle (in terms of N/Y/G for each letter of
-    /// the guess), update the state.
-    fn update(&mut self, word: &Word, response: &str) {
-        if response.chars().nth(0).unwrap() != 'N' {
-            self.in_target.add(word.c0);
-            if response.chars().nth(0).unwrap() == 'Y' {
-                self.c0.remove(word.c0);
-            }
-            else {
-                self.c0.remove_others(word.c0);
-            }
-        }
-        else {
-            self.remove_letter(word.c0);
-        }
-        if response.chars().nth(1).unwrap() != 'N' {
-            self.in_target.add(word.c1);
-            if response.chars().nth(1).unwrap() == 'Y' {
-                self.c1.remove(word.c1);
-            }
-            else {
-                self.c1.remove_others(word.c1);
-            }
+    /// a solver for the word lists. Strategies that compare a lot of words get the pattern
+    /// matrix, which is cached between runs.
+    fn solver(&self) -> Result<Solver, Error> {
+        let answers = self.answers()?;
+        let guesses = self.guesses()?;
+        let patterns = if self.strategy.uses_patterns() {
+            Some(PatternMatrix::load_or_build(&guesses, &answers))
         }
         else {
-            self.remove_letter(word.c1);
-        }
-        if response.chars().nth(2).unwrap() != 'N' {
-            self.in_target.add(word.c2);
-            if response.chars().nth(2).unwrap() == 'Y' {
-                self.c2.remove(word.c2);
-            }
-            else {
-                self.c2.remove_others(word.c2);
-            }
-        }
-        else {
-            self.remove_letter(word.c2);
-        }
-        if response.chars().nth(3).unwrap() != 'N' {
-            self.in_target.add(word.c3);
-            if response.chars().nth(3).unwrap() == 'Y' {
-                self.c3.remove(word.c3);
-            }
-            else {
-                self.c3.remove_others(word.c3);
-            }
-        }
-        else {
-            self.remove_letter(word.c3);
-        }
-        if response.chars().nth(4).unwrap() != 'N' {
-            self.in_target.add(word.c4);
-            if response.chars().nth(4).unwrap() == 'Y' {
-                self.c4.remove(word.c4);
-            }
-            else {
-                self.c4.remove_others(word.c4);
+            None
+        };
+        let mut solver = Solver::new(answers)
+            .with_guesses(guesses)
+            .with_strategy(self.strategy);
+        if let Some(patterns) = patterns {
+            solver = solver.with_patterns(Arc::new(patterns));
+        }
+        if let Some(path) = &self.tree {
+            let tree = DecisionTree::load(path)?;
+            if tree.first_guess().len() != self.word_len {
+                return Err(Error::Invalid(format!("{} is not a tree for {} letter words", path.display(), self.word_len)));
             }
+            solver = solver.with_tree(Arc::new(tree));
         }
-        else {
-            self.remove_letter(word.c4);
-        }
-
+        Ok(solver)
     }
 
-    fn is_usable(&self, c: char) -> bool {
-        self.c0.contains(c) || self.c1.contains(c) || self.c2.contains(c) || self.c3.contains(c) || self.c4.contains(c)
+    /// the session to record the game in: the one to resume, or a new one.
+    fn session(&self) -> Result<Session, Error> {
+        if self.resume {
+            return Session::open(&self.session_file);
+        }
+        if let Some(dir) = self.session_file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| Error::file(dir, e))?;
+        }
+        Session::create(&self.session_file)
     }
 }
 
-/// a pair of character and the number of times it appears in the word list.
-#[derive(Debug)]
-struct CharCount {
-    c : u32,
-    count : u32
+/// where the game is recorded unless told otherwise: `$XDG_STATE_HOME/wordlers`, falling back to
+/// `~/.local/state/wordlers` and then the temporary directory.
+fn default_session_file() -> PathBuf {
+    let dir = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("state")))
+        .unwrap_or_else(std::env::temp_dir);
+    dir.join("wordlers").join("session.jsonl")
 }
 
-
-/// the allowed collection of words.
-struct WordCollection {
-    words: Vec<Word>,
+/// reads a line from the standard input, or None at the end of the input.
+fn read_line() -> Result<Option<String>, Error> {
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line)),
+    }
 }
 
-impl WordCollection {
-    /// reads five lettered words from the file.
-    fn new(filename: &str) -> WordCollection {
-        let mut words = Vec::new();
-        let path = Path::new(filename);
-
-        let lines = match File::open(&path) {
-            Err(why) => panic!("couldn't open {}: {}", path.display(),  why),
-            Ok(file) => io::BufReader::new(file).lines(),
+/// keeps suggesting words until wordle says GGGGG or we run out of words. The first few guesses
+/// come from the `opening`. Every step is recorded in the session file, and a resumed session is
+/// replayed first. The game stops quietly at the end of the input, to be resumed later.
+fn solve(opening: &Opening, config: &Config) -> Result<(), Error> {
+    let mut session = match config.session() {
+        Ok(session) => session,
+        Err(e) if config.resume => return Err(e),
+        Err(e) => {
+            println!("{}", format!("the game will not be saved: {}", e).red());
+            Session::new()
+        }
+    };
+    if session.steps().iter().any(|(guess, _)| guess.len() != config.word_len) {
+        return Err(Error::Invalid(format!("the session is not for {} letter words", config.word_len)));
+    }
+    if session.steps().last().is_some_and(|(_, response)| response.is_solved()) {
+        println!("this game is already solved");
+        return Ok(());
+    }
+    let mut solver = config.solver()?;
+    session.replay(&mut solver)?;
+    loop {
+        let turn = session.steps().len();
+        let known = session.steps().iter().map(|(_, response)| response.known_count()).sum();
+        let mut guess = match opening.opener(turn, known).or_else(|| solver.suggest()) {
+            Some(guess) => guess,
+            None => break,
         };
-
-
-        for line in lines {
-            if let Ok(x) = line  {
-                if x.len() == 5 {
-                    words.push(Word::new(&x));
+        println!("{}", guess.to_string().to_uppercase().green().bold());
+        let response = loop {
+            match read_response(&solver)? {
+                None => return Ok(()),
+                Some(Reply::Response(response)) => break Some(response),
+                Some(Reply::Played(word)) => {
+                    guess = word;
+                    println!("the response to {} then?", guess.to_string().to_uppercase().bold());
                 }
+                Some(Reply::Undo) => break None,
             }
+        };
+        let Some(response) = response else {
+            take_back(&mut session, &mut solver);
+            continue;
+        };
+        if let Err(e) = session.record(&guess, &response) {
+            println!("{}", format!("could not save the step: {}", e).red());
         }
-
-        WordCollection {
-            words: words
-        }
-    }
-
-    /// returns a random word from the collection.
-    /// Useful for playing wordle/cows and bulls.
-    fn get_random_word(&self) -> String {
-        let mut rng = thread_rng();
-        let index = rng.gen_range(0..self.words.len());
-        self.words[index].to_string()
-    }
-
-    /// returns true if the given word is in the collection.
-    fn contains_word(&self, word: &str) -> bool {
-        for w in &self.words {
-            if w.to_string() == word.to_lowercase() {
-                return true;
+        if response.is_solved() {
+            println!("thank you!");
+            return Ok(());
+        }
+        solver.feedback(&guess, &response)?;
+        if solver.candidates().is_empty() {
+            explain_inconsistency(&config.answers()?, session.steps());
+            println!("?U takes the last response back");
+            match read_response(&solver)? {
+                Some(Reply::Undo) => take_back(&mut session, &mut solver),
+                _ => break,
             }
         }
-        false
     }
+    println!("{}", "I give up".red());
+    Ok(())
+}
 
-    fn from_words(words: Vec<Word>) -> WordCollection {
-        WordCollection {
-            words: words
-        }
+/// takes the last response back, in the session and in the solver.
+fn take_back(session: &mut Session, solver: &mut Solver) {
+    match session.undo() {
+        Ok(Some((guess, response))) => println!("took back {} {}", guess.to_string().to_uppercase(), response),
+        Ok(None) => println!("{}", "there is nothing to take back".red()),
+        Err(e) => println!("{}", format!("could not save the session: {}", e).red()),
     }
+    solver.undo();
+}
 
-    /// returns the best guess for the next word for wordle.
-    fn get_best_word(&self) -> Word {
-        if self.words.len() == 0 {
-            println!("{}", "I give up".red());
-            return Word::new("     ");
-        }
-        let mut vec = Vec::new();
-        for c in 0..26 {
-            vec.push(CharCount {
-                c: c as u32,
-                count: 0
-            });
-        }
-        for word in &self.words {
-            for c in word.to_string().chars() {
-                vec[c as usize - 'a' as usize].count += 1;
-            }
-        }
-        vec.sort_by(|a, b| a.count.cmp(&b.count));
-        let mut scores = Vec::new();
-        for c in 0..26 {
-            let i = vec.iter().position(|x| x.c == c as u32).unwrap();
-            scores.push(i);
-        }
-        let mut best_word = self.words[0];
-        let mut score = best_word.score(&scores);
-        for i in 1..self.words.len() {
-            let word = self.words[i];
-            let new_score = word.score(&scores);
-            if new_score > score {
-                best_word = word;
-                score = new_score;
-            }
-        }
-        best_word
+/// tells which step left no word and which single tile typos would explain it.
+fn explain_inconsistency(answers: &WordCollection, history: &[(Word, Feedback)]) {
+    let Some(inconsistency) = find_inconsistency(answers, history) else {
+        return;
+    };
+    let (guess, response) = history[inconsistency.step];
+    let guess = guess.to_string().to_uppercase();
+    if inconsistency.contradictory {
+        println!("{}", format!("{} {} contradicts the responses before it", guess, response).red());
     }
-
-    /// returns a new collection with only those words that satisfy the current state.
-    /// This is called after the state has been updated by the wordle response.
-    fn filter(&self, state: &WordleState) -> WordCollection {
-        let mut words = Vec::new();
-        for word in &self.words {
-            if state.is_allowed(&word) {
-                words.push(word.clone());
-            }
-        }
-        WordCollection::from_words(words)
+    else {
+        println!("{}", format!("no word in the list fits {} {} and the responses before it", guess, response).red());
     }
-
-    fn remove(&mut self, word: &Word) {
-        self.words.retain(|x| x != word);
+    if !inconsistency.fixes.is_empty() {
+        println!("did you mean");
     }
-}
-
-/// downloads sgb word file from Knuth's site.
-fn download_file_if_needed(url: &str, filename: &str) {
-    let path = Path::new(filename);
-    if !path.exists() {
-        let mut file = File::create(filename).unwrap();
-        let mut response = reqwest::blocking::get(url).unwrap();
-        io::copy(&mut response, &mut file).unwrap();
+    for (step, fixed, left) in inconsistency.fixes.iter().take(5) {
+        let (guess, response) = history[*step];
+        println!("  {} {} instead of {} ({} words left)", guess.to_string().to_uppercase(), fixed.to_string().bold(), response, left);
     }
 }
 
-/// tries to solve the wordle.
-fn solve_wordle() {
-    let mut collection = WordCollection::new("sgb-words.txt");
-    let mut word = collection.get_best_word();
-    let mut state = WordleState::new();
-    while collection.words.len() > 0 {
-        println!("{}", word.to_string().to_uppercase().green().bold());
-        let mut response = String::new();
-        io::stdin().read_line(&mut response).unwrap();
-        let response = response.trim().to_uppercase();
-        state.update(&word, &response);
-        collection = collection.filter(&state);
-        word = collection.get_best_word();
-        if response == "GGGGG" {
-            println!("thank you!");
-            break;
-        }
-    }
+/// shows how the candidates shrank at each step of a saved session.
+fn replay_session(file: &Path, format: Format, config: &Config) -> Result<(), Error> {
+    let session = Session::open(file)?;
+    if session.steps().iter().any(|(guess, _)| guess.len() != config.word_len) {
+        return Err(Error::Invalid(format!("the session is not for {} letter words", config.word_len)));
+    }
+    let mut solver = config.solver()?;
+    let words = solver.candidates().len();
+    let counts = session.replay(&mut solver)?;
+    if format == Format::Json {
+        let steps: Vec<_> = session.steps().iter().zip(counts)
+            .map(|((guess, response), count)| json!({ "guess": guess.to_string(), "response": response.to_string(), "left": count }))
+            .collect();
+        println!("{:#}", json!({ "words": words, "steps": steps }));
+        return Ok(());
+    }
+    println!("{} words", words);
+    for ((guess, response), count) in session.steps().iter().zip(counts) {
+        println!("{} {} {} words left", guess.to_string().to_uppercase().bold(), response, count);
+    }
+    Ok(())
 }
-/// tries to solve the wordle in soft-mode.
-fn solve_wordle_soft_mode() {
-    let adieu = Word::new("adieu");
-    let pylon = Word::new("pylon");
-    let crows = Word::new("crows");
-    let fight = Word::new("fight");
-    let mut collection = WordCollection::new("sgb-words.txt");
-    let mut state = WordleState::new();
-    println!("{}", adieu.to_string().to_uppercase().green().bold());
-    let response = read_response(&collection, &state);
-    state.update(&adieu, &response);
-    if response == "GGGGG" {
-        println!("thank you!");
-        return;
-    }
-    let mut known_count = 0;
-    for c in response.chars() {
-        if c != 'N' {
-            known_count += 1;
-        }
-    }
-    collection = collection.filter(&state);
-    if known_count < 4 {
-        println!("{}", pylon.to_string().to_uppercase().green().bold());
-        let response = read_response(&collection, &state);
-        state.update(&pylon, &response);
-        if response == "GGGGG" {
-            println!("thank you!");
-            return;
-        }
-        for c in response.chars() {
-            if c != 'N' {
-                known_count += 1;
-            }
-        }
-        collection = collection.filter(&state);
-    }
-    if known_count < 4 {
-        println!("{}", crows.to_string().to_uppercase().green().bold());
-        let response = read_response(&collection, &state);
-        state.update(&crows, &response);
-        if response == "GGGGG" {
-            println!("thank you!");
-            return;
-        }
-        for c in response.chars() {
-            if c != 'N' {
-                known_count += 1;
+
+/// solves several boards at once. Every guess is followed by one response for each board that is
+/// not solved yet.
+fn solve_multi(boards: usize, config: &Config) -> Result<(), Error> {
+    let mut solver = MultiSolver::new(config.solver()?, boards);
+    while !solver.is_done() {
+        let guess = match solver.suggest() {
+            Some(guess) => guess,
+            None => {
+                println!("{}", "I give up".red());
+                return Ok(());
             }
+        };
+        println!("{}", guess.to_string().to_uppercase().green().bold());
+        for board in solver.unsolved() {
+            println!("board {}", board + 1);
+            let response = loop {
+                match read_response(&solver.boards()[board])? {
+                    None => return Ok(()),
+                    Some(Reply::Response(response)) => break response,
+                    Some(_) => println!("{}", "with several boards the guess cannot be changed or taken back".red()),
+                }
+            };
+            solver.feedback(board, &guess, &response)?;
         }
-        collection = collection.filter(&state);
-    }
-    if known_count < 4 {
-        println!("{}", fight.to_string().to_uppercase().green().bold());
-        let response = read_response(&collection, &state);
-        state.update(&fight, &response);
-        if response == "GGGGG" {
-            println!("thank you!");
-            return;
-        }
-        collection = collection.filter(&state);
-    }
-    let mut word = collection.get_best_word();
-    while collection.words.len() > 0 {
-        println!("{}", word.to_string().to_uppercase().green().bold());
-        let response = read_response(&collection, &state);
-        if response == "GGGGG" {
-            println!("thank you!");
-            break;
-        }
-        state.update(&word, &response);
-        collection = collection.filter(&state);
-        word = collection.get_best_word();
     }
+    println!("thank you!");
+    Ok(())
 }
 
-fn read_response(words: &WordCollection, state: &WordleState) -> String {
-    loop {
-        let mut response = String::new();
-        io::stdin().read_line(&mut response).unwrap();
+/// what the player said after a guess was suggested.
+enum Reply {
+    /// the response wordle gave.
+    Response(Feedback),
+    /// another word was played instead of the suggestion.
+    Played(Word),
+    /// the last response was wrong and should be taken back.
+    Undo,
+}
+
+/// reads the response to a guess, written as G/Y/N letters or emoji squares, answering the ?C, ?L
+/// and ?H questions along the way. ?W word tells that another word was played and ?U takes the
+/// last response back. None at the end of the input.
+fn read_response(solver: &Solver) -> Result<Option<Reply>, Error> {
+    let word_len = solver.candidates().word_len();
+    while let Some(response) = read_line()? {
         let x = response.trim().to_uppercase();
-        if x.len() == 5 {
-            return x;
+        if x == "?U" {
+            return Ok(Some(Reply::Undo));
+        }
+        else if let Some(word) = x.strip_prefix("?W") {
+            match Word::new(&word.trim().to_lowercase()) {
+                Ok(word) if word.len() == word_len => return Ok(Some(Reply::Played(word))),
+                _ => println!("{}", format!("?W takes the {} letter word that was played", word_len).red()),
+            }
         }
         else if x == "?C" {
-            println!("{} words", words.words.len());
+            println!("{} words", solver.candidates().len());
         }
         else if x == "?L" {
-            for word in &words.words {
-                print!("{} ", word.to_string());
+            for word in solver.candidates().words() {
+                print!("{} ", word);
             }
-            println!("");
+            println!();
         }
         else if x == "?H" {
-            let alphabet = "abcsdefghijklmnopqrstuvwxyz";
-            for c in alphabet.chars() {
-                if state.is_usable(c) {
+            for c in 'a'..='z' {
+                if solver.state().is_usable(c) {
                     print!("{} ", c);
                 }
             }
-            println!("");
+            println!();
         }
         else {
-            println!("{}", "please enter five letters".red());
+            match x.parse::<Feedback>() {
+                Ok(feedback) if feedback.len() == word_len => return Ok(Some(Reply::Response(feedback))),
+                Ok(_) => println!("{}", format!("please enter {} letters", word_len).red()),
+                Err(e) => println!("{}", e.to_string().red()),
+            }
         }
     }
+    Ok(None)
 }
 
-fn solve_worlde_with(first_word: &str) {
-    let mut collection = WordCollection::new("sgb-words.txt");
-    let mut word = Word::new(first_word);
-    let mut state = WordleState::new();
-    while collection.words.len() > 0 {
-        println!("{}", word.to_string().to_uppercase().green().bold());
-        let response = read_response(&collection, &state); //response.trim().to_uppercase();
-        state.update(&word, &response);
-        collection = collection.filter(&state);
-        word = collection.get_best_word();
-        if response == "GGGGG" {
-            println!("thank you!");
-            break;
-        }
-    }
+/// plays against every answer in the list and reports how many guesses it took.
+fn run_bench(opening: &Opening, format: Format, config: &Config) -> Result<(), Error> {
+    let solver = config.solver()?;
+    let report = play_games(&solver, opening, config.answers()?.words());
+    if format == Format::Json {
+        let games: Vec<_> = report.games.iter()
+            .map(|(word, guesses)| json!({ "word": word.to_string(), "guesses": guesses }))
+            .collect();
+        println!("{:#}", json!({ "average": report.average(), "histogram": report.histogram(), "games": games }));
+        return Ok(());
+    }
+    println!("{} games, {:.3} guesses on average", report.games.len(), report.average());
+    let histogram = report.histogram();
+    let most = histogram.iter().copied().max().unwrap_or(0).max(1);
+    for (guesses, count) in histogram.iter().enumerate().skip(1) {
+        if *count > 0 {
+            println!("{:>2}: {:>5} {}", guesses, count, "#".repeat((count * 50).div_ceil(most)));
+        }
+    }
+    let failures = report.failures();
+    println!("{} games needed more than six guesses", failures.len().to_string().red());
+    for (word, guesses) in report.worst(10) {
+        match guesses {
+            Some(guesses) => println!("{} {}", word, guesses),
+            None => println!("{} {}", word, "not found".red()),
+        }
+    }
+    Ok(())
 }
 
-/*
-fn next_pos_and_update(given: &str, c: char, visited:&mut Vec<bool>) -> (usize, bool) {
-    let mut pos = given.len();
-    let mut found = false;
-    for i in 0..given.len() {
-        if visited[i] {
-            continue;
-        }
-        if given.chars().nth(i).unwrap() == c {
-            pos = i;
-            found = true;
-            break;
-        }
-    }
-    if found {
-        visited[pos] = true;
-    }
-    (pos, found)
-}*/
-
-fn not_found_in(given: &str, c: char) -> bool {
-    for i in 0..given.len() {
-        if given.chars().nth(i).unwrap() == c {
-            return false;
-        }
-    }
-    true
+/// benches every strategy on the same answers and prints the average and the most guesses each
+/// one took, to compare the worst case of minimax with the others.
+fn compare_strategies(opening: &Opening, format: Format, config: &Config) -> Result<(), Error> {
+    let answers = config.answers()?;
+    let mut reports = Vec::new();
+    for strategy in Strategy::ALL {
+        let solver = Config { strategy, tree: None, ..config.clone() }.solver()?;
+        reports.push((strategy, play_games(&solver, opening, answers.words())));
+    }
+    if format == Format::Json {
+        let strategies: Vec<_> = reports.iter()
+            .map(|(strategy, report)| json!({
+                "strategy": strategy.to_string(),
+                "average": report.average(),
+                "most_guesses": report.most_guesses(),
+                "failures": report.failures().len(),
+            }))
+            .collect();
+        println!("{:#}", json!(strategies));
+        return Ok(());
+    }
+    println!("{} games", answers.len());
+    println!("{:<10} {:>8} {:>5} {:>9}", "strategy", "average", "most", "failures");
+    for (strategy, report) in &reports {
+        let most = report.most_guesses().map_or("-".to_string(), |most| most.to_string());
+        println!("{:<10} {:>8.3} {:>5} {:>9}", strategy.to_string(), report.average(), most, report.failures().len());
+    }
+    Ok(())
 }
 
-fn wordle_compare(given: &str, word: &str) -> String {
-    let mut chars = Vec::new();
-    let mut visited = Vec::new();
-    for _ in word.chars() {
-        chars.push('.');
-        visited.push(false);
-    }
-    for i in 0..word.len() {
-        let c = word.chars().nth(i).unwrap();
-        if not_found_in(given, c) {
-            chars[i] = 'N';
-        }
-    }
-    for i in 0..word.len() {
-        let c = word.chars().nth(i).unwrap();
-        if given.chars().nth(i).unwrap() ==  c {
-            chars[i] = 'G';
-            visited[i] = true;
-        }
-    }
-    for i in 0..word.len() {
-        if chars[i] == '.' {
-            let mut found = false;
-            let x = word.chars().nth(i).unwrap();
-            for j in 0..word.len() {
-                if  i == j || visited[j] {
-                    continue;
-                }
-                let c = given.chars().nth(j).unwrap();
-                if c == x  {
-                    found = true;
-                    visited[j] = true;
-                    break;
-                }
-            }
-            if found {
-                chars[i] = 'Y';
-            }
-            else {
-                chars[i] = 'N';
+/// works out the best decision tree for the word lists, saves it if asked to, and prints how it
+/// fares next to the strategy.
+fn build_tree(start: Option<&str>, objective: Objective, breadth: Limit, save: Option<&Path>, format: Format, config: &Config) -> Result<(), Error> {
+    let answers = config.answers()?;
+    let guesses = config.guesses()?;
+    let patterns = PatternMatrix::load_or_build(&guesses, &answers);
+    let mut search = TreeSearch::new(&patterns).with_objective(objective);
+    match breadth.0 {
+        Some(0) => return Err(Error::Invalid("the breadth takes a number from 1 up, or all".to_string())),
+        Some(breadth) => search = search.with_breadth(breadth),
+        None => {}
+    }
+    let opening = match start {
+        Some(start) => {
+            let start = Word::new(&start.to_lowercase())?;
+            if !guesses.words().contains(&start) {
+                return Err(Error::Invalid(format!("{} is not one of the guesses", start)));
             }
-        }
-    }
-    let mut response = String::new();
-    for c in chars {
-        response.push(c);
-    }
-    response
+            search = search.with_start(&start);
+            Opening::Word(start)
+        }
+        None => Opening::Solver,
+    };
+    let Some(tree) = search.build() else {
+        return Err(Error::Invalid(format!("there is no tree that solves every answer in {} guesses", GIVE_UP_AFTER)));
+    };
+    if let Some(path) = save {
+        tree.save(path)?;
+    }
+    let solver = Config { tree: None, ..config.clone() }.solver()?;
+    let report = play_games(&solver, &opening, answers.words());
+    if format == Format::Json {
+        println!("{:#}", json!({
+            "answers": tree.answers(),
+            "tree": {
+                "first_guess": tree.first_guess().to_string(),
+                "average": tree.average(),
+                "total": tree.total_guesses(),
+                "most_guesses": tree.most_guesses(),
+            },
+            "strategy": {
+                "name": config.strategy.to_string(),
+                "average": report.average(),
+                "most_guesses": report.most_guesses(),
+            },
+        }));
+        return Ok(());
+    }
+    let most = report.most_guesses().map_or("-".to_string(), |most| most.to_string());
+    println!("{} answers", tree.answers());
+    println!("the tree starts with {} and takes {:.3} guesses on average, {} at most",
+             tree.first_guess().to_string().to_uppercase().green().bold(), tree.average(), tree.most_guesses());
+    println!("{} takes {:.3} guesses on average, {} at most", config.strategy, report.average(), most);
+    Ok(())
 }
+
+/// the opening taken by bench and compare: soft, or the first word to play.
+fn bench_opening(opening: Option<&str>, word_len: usize) -> Result<Opening, Error> {
+    Ok(match opening {
+        None => Opening::Solver,
+        Some("soft") if word_len == 5 => Opening::Soft,
+        Some("soft") => return Err(soft_needs_five()),
+        Some(word) => match Word::new(&word.to_lowercase()) {
+            Ok(word) if word.len() == word_len => Opening::Word(word),
+            _ => return Err(Error::Invalid(format!("bench takes soft or a {} letter word", word_len))),
+        },
+    })
+}
+
+fn soft_needs_five() -> Error {
+    Error::Invalid("soft mode only works with five letter words".to_string())
+}
+
 /// helper function to display the response for a guess in Cows and Bulls.
 fn compare_print_cb(given: &str, response: &str) {
-    let mut cows = 0;
-    let mut bulls = 0;
-    for i in 0..5 {
-        let r = response.chars().nth(i).unwrap();
-        if r == given.chars().nth(i).unwrap() {
-            bulls += 1;
-        }
-        else {
-            let mut found = false;
-            for j in 0..5 {
-                if i == j {
-                    continue;
-                }
-                if given.chars().nth(j).unwrap() == r {
-                    found = true;
-                    break;
-                }
-            }
-            if found {
-                cows += 1;
-            }
-        }
-    }
+    let (bulls, cows) = cows_and_bulls(given, response);
     println!("{} bulls and {} cows", bulls, cows);
 }
 
-/// helper function to display the response for a guess in Wordle.
-fn compare_print_wordle(given: &str, response: &str) {
-    let text = wordle_compare(given, response);
-    println!("{}", text.bold());
+/// a random answer to play against, drawn from `seed` when there is one.
+fn random_answer(config: &Config, seed: Option<u64>) -> Result<String, Error> {
+    let answers = config.answers()?;
+    if answers.is_empty() {
+        return Err(Error::Invalid(format!("there are no {} letter answers", config.word_len)));
+    }
+    Ok(match seed {
+        Some(seed) => answers.random_word(&mut StdRng::seed_from_u64(seed)),
+        None => answers.get_random_word(),
+    })
 }
 
-/// to play wordle.
-fn play_wordle() {
+/// to play wordle. In `hard` mode every guess must use the hints given so far.
+fn play_wordle(config: &Config, hard: bool, seed: Option<u64>) -> Result<(), Error> {
     println!("{}", "This is a cheap knock off to the excellent https://powerlanguage.co.uk/wordle/ ".green().bold());
-    println!("{}", "Consider playing there.");
+    println!("Consider playing there.");
     println!();
     println!("enter your word");
-    let collection = WordCollection::new("sgb-words.txt");
-    let word = collection.get_random_word();
+    let word_len = config.word_len;
+    let collection = config.guesses()?;
+    let word = random_answer(config, seed)?;
+    let mut history = Vec::new();
     let mut count : u32 = 0;
     while count < 6 {
-        let mut response = String::new();
-        io::stdin().read_line(&mut response).unwrap();
+        let Some(response) = read_line()? else {
+            break;
+        };
         let response = response.trim().to_lowercase();
         if response == "quit" {
             break;
         }
-        if response.len() != 5 {
-            println!("{}", "Please enter a 5 letter word".red());
+        if response.len() != word_len {
+            println!("{}", format!("Please enter a {} letter word", word_len).red());
             continue;
         }
         if !collection.contains_word(&response) {
             println!("{}", "Nope! there ain't no such word.".red());
             continue;
         }
+        let guess = Word::new(&response)?;
+        if hard && !history.iter().all(|(previous, feedback)| uses_hints(&guess, previous, feedback)) {
+            println!("{}", "In hard mode every hint must be used".red());
+            continue;
+        }
         if response == word {
             println!("{} indeed", response.green());
             break;
         }
-        else {
-            compare_print_wordle(&word, &response);
-        }
-        count = count + 1;
+        let feedback = wordle_compare(&word, &response)?;
+        println!("{}", feedback.to_string().bold());
+        history.push((guess, feedback));
+        count += 1;
     }
     if count > 5 {
-        println!("{} {}", "Loser!".red(), word.to_string().blue());
+        println!("{} {}", "Loser!".red(), word.blue());
+    }
+    Ok(())
+}
+
+/// to play absurdle, where the game keeps changing its mind about the target.
+fn play_absurdle(config: &Config) -> Result<(), Error> {
+    println!("{}", "Welcome to absurdle! There is no target until you corner one.".green().bold());
+    println!();
+    println!("enter your word");
+    let word_len = config.word_len;
+    let collection = config.guesses()?;
+    let mut game = Absurdle::new(config.answers()?);
+    let mut count : u32 = 0;
+    loop {
+        let response = read_line()?.unwrap_or_else(|| "quit".to_string());
+        let response = response.trim().to_lowercase();
+        if response == "quit" {
+            println!("{} words were still possible", game.candidates().len().to_string().blue());
+            break;
+        }
+        if response.len() != word_len {
+            println!("{}", format!("Please enter a {} letter word", word_len).red());
+            continue;
+        }
+        if !collection.contains_word(&response) {
+            println!("{}", "Nope! there ain't no such word.".red());
+            continue;
+        }
+        count += 1;
+        let text = game.guess(&Word::new(&response)?);
+        if text.is_solved() {
+            println!("{} indeed, in {} guesses", response.green(), count);
+            break;
+        }
+        println!("{}", text.to_string().bold());
+    }
+    Ok(())
+}
+
+/// how `shapes` searches: the most solutions to print (None for all), whether the guesses must
+/// be valid in hard mode and whether the most common words come first.
+struct ShapeOptions {
+    limit: Option<usize>,
+    hard_mode: bool,
+    ranked: bool,
+}
+
+impl ShapeOptions {
+    fn search(&self, target: Word, rows: Vec<Feedback>, words: &WordCollection) -> ShapeSearch {
+        let search = ShapeSearch::new(target, rows, words)
+            .with_hard_mode(self.hard_mode)
+            .with_ranking(self.ranked);
+        match self.limit {
+            Some(limit) => search.with_limit(limit),
+            None => search,
+        }
+    }
+}
+
+fn find_shape(final_word: &str, shape: &[String], options: &ShapeOptions, format: Format, config: &Config) -> Result<(), Error> {
+    let target = Word::new(&final_word.to_lowercase())?;
+    let mut rows = Vec::new();
+    for row in shape {
+        match row.parse::<Feedback>() {
+            Ok(target) if target.len() == final_word.len() => rows.push(target),
+            Ok(_) => return Err(Error::Invalid(format!("{} does not have {} letters", row, final_word.len()))),
+            Err(e) => return Err(Error::Parse(format!("{}: {}", row, e))),
+        }
+    }
+    let collection = Config { word_len: final_word.len(), ..config.clone() }.guesses()?;
+    let search = options.search(target, rows.clone(), &collection);
+    let impossible = search.impossible_rows();
+    if format == Format::Json {
+        let solutions = if impossible.is_empty() { search.solutions() } else { Vec::new() };
+        let solutions: Vec<Vec<String>> = solutions.iter()
+            .map(|solution| solution.iter().map(Word::to_string).collect())
+            .collect();
+        let impossible: Vec<String> = impossible.iter().map(Feedback::to_string).collect();
+        println!("{:#}", json!({ "impossible_rows": impossible, "solutions": solutions }));
+        return Ok(());
+    }
+    if !impossible.is_empty() {
+        for row in impossible {
+            println!("no word matching {}", row);
+        }
+        return Ok(());
+    }
+    let solutions = search.solutions();
+    if solutions.is_empty() {
+        println!("{}", "no distinct words draw the shape".red());
     }
+    for (i, solution) in solutions.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        for (row, word) in rows.iter().zip(solution) {
+            println!("{} {}", row, word);
+        }
+    }
+    Ok(())
 }
 
-fn find_shape(final_word: &str, shape: Vec<String>) {
-    let mut collection = WordCollection::new("sgb-words.txt");
-    
-    for target in shape {
-        let mut maybe_word : Option<Word> = None;
-        for word in &collection.words {
-            if wordle_compare( final_word, &word.to_string()) == target {
-                println!("{} {}", target, word.to_string());
-                maybe_word = Some(word.clone());
-                break;
+/// draws a picture on as many boards as it takes. `source` is a PBM or ASCII art file, or else
+/// text to write in `ink`. Rows that no word can give are replaced by the closest ones that can.
+fn draw_picture(final_word: &str, text: &str, file: Option<&Path>, ink: Tile, options: &ShapeOptions, config: &Config) -> Result<(), Error> {
+    let target = Word::new(&final_word.to_lowercase())?;
+    let picture = match file {
+        Some(path) => {
+            let data = std::fs::read(path).map_err(|e| Error::file(path, e))?;
+            if data.starts_with(b"P1") || data.starts_with(b"P4") {
+                Picture::from_pbm(&data, ink).map_err(|e| Error::Parse(format!("{}: {}", path.display(), e)))?
+            }
+            else {
+                Picture::from_ascii(&String::from_utf8_lossy(&data))
             }
         }
-        if maybe_word.is_none() {
-            println!("no word matching {}", target);
+        None => Picture::from_text(text, ink).map_err(Error::Invalid)?,
+    };
+    let collection = Config { word_len: target.len(), ..config.clone() }.guesses()?;
+    for (i, wanted) in picture.boards(target.len(), MAX_GUESSES).into_iter().enumerate() {
+        println!("board {}", i + 1);
+        let Some(rows) = nearest_patterns(&target, &wanted, &collection) else {
+            println!("{}", "there are not enough words to draw this board".red());
+            continue;
+        };
+        let solution = options.search(target, rows.clone(), &collection).with_limit(1).solutions();
+        let Some(words) = solution.first() else {
+            println!("{}", "no distinct words draw this board".red());
+            continue;
+        };
+        for ((row, wanted), word) in rows.iter().zip(&wanted).zip(words) {
+            let squares: String = row.tiles().iter().map(|tile| tile.to_emoji()).collect();
+            if row == wanted {
+                println!("{} {}", squares, word);
+            }
+            else {
+                println!("{} {} (instead of {})", squares, word, wanted);
+            }
         }
-        else {
-            let word = maybe_word.unwrap();
-            collection.remove(&word);
+    }
+    Ok(())
+}
+
+/// reads a pasted share block from the standard input. It ends at the solved row, at a blank
+/// line after the rows or at the end of the input.
+fn read_share_grid() -> Result<ShareGrid, Error> {
+    let mut text = String::new();
+    let mut rows = 0;
+    while let Some(line) = read_line()? {
+        match Feedback::from_squares(&line) {
+            Ok(row) if !row.is_empty() => {
+                rows += 1;
+                text.push_str(&line);
+                if row.is_solved() {
+                    break;
+                }
+            }
+            _ if rows > 0 && line.trim().is_empty() => break,
+            _ => text.push_str(&line),
+        }
+    }
+    Ok(text.parse()?)
+}
+
+/// reads a word with `word_len` letters from the standard input, or None at the end of the input.
+fn read_word(word_len: usize) -> Result<Option<Word>, Error> {
+    while let Some(word) = read_line()? {
+        match Word::new(&word.trim().to_lowercase()) {
+            Ok(word) if word.len() == word_len => return Ok(Some(word)),
+            _ => println!("{}", format!("please enter {} letters", word_len).red()),
         }
     }
+    Ok(None)
+}
+
+/// replays a shared result: reads the share block, then the word played on each row, and shows
+/// how many words were left after each of them.
+fn import_share(config: &Config) -> Result<(), Error> {
+    println!("paste the shared result");
+    let grid = read_share_grid()?;
+    if grid.word_len() != config.word_len {
+        return Err(Error::Invalid(format!("the rows have {} squares, not {}", grid.word_len(), config.word_len)));
+    }
+    if let Some(title) = &grid.title {
+        println!("{}", title.bold());
+    }
+    let mut solver = config.solver()?;
+    for row in &grid.rows {
+        if row.is_solved() {
+            println!("{} solved, out of {} words", row.to_string().green().bold(), solver.candidates().len());
+            return Ok(());
+        }
+        println!("{} which word was played?", row.to_string().bold());
+        let Some(guess) = read_word(config.word_len)? else {
+            return Ok(());
+        };
+        solver.feedback(&guess, row)?;
+        println!("{} words left", solver.candidates().len());
+    }
+    match solver.suggest() {
+        Some(guess) => println!("next time try {}", guess.to_string().to_uppercase().green().bold()),
+        None => println!("{}", "no word fits these responses".red()),
+    }
+    Ok(())
+}
+
+/// guesses the answer of a puzzle from the results other people shared, pasted one after the
+/// other until the end of the input.
+fn infer_shared(format: Format, config: &Config) -> Result<(), Error> {
+    if format == Format::Text {
+        println!("paste the shared results, then end the input (ctrl-d)");
+    }
+    let grids = ShareGrid::parse_all(&io::read_to_string(io::stdin())?)?;
+    if grids.is_empty() {
+        return Err(Error::Parse("there are no results to go on".to_string()));
+    }
+    if let Some(grid) = grids.iter().find(|grid| grid.word_len() != config.word_len) {
+        return Err(Error::Invalid(format!("a result has {} squares a row, not {}", grid.word_len(), config.word_len)));
+    }
+    let survivors = infer_answers(&grids, &config.answers()?, &config.guesses()?);
+    if format == Format::Json {
+        let survivors: Vec<_> = survivors.iter()
+            .map(|(word, score)| json!({ "word": word.to_string(), "score": score }))
+            .collect();
+        println!("{:#}", json!(survivors));
+        return Ok(());
+    }
+    println!("{} answers fit the {} results", survivors.len(), grids.len());
+    for (word, score) in survivors.iter().take(10) {
+        println!("{} {:.1}", word.to_string().to_uppercase().green().bold(), score);
+    }
+    Ok(())
 }
 
 /// to play Cows and Bulls.
-fn play_cows_and_bulls() {
+fn play_cows_and_bulls(config: &Config, seed: Option<u64>) -> Result<(), Error> {
     println!("{}", "Welcome to cows and bulls! ".green().bold());
     println!();
     println!("enter your word");
-    let collection = WordCollection::new("sgb-words.txt");
-    let word = collection.get_random_word();
+    let word_len = config.word_len;
+    let collection = config.guesses()?;
+    let word = random_answer(config, seed)?;
     let mut count : u32 = 0;
     while count < 20 {
-        let mut response = String::new();
-        io::stdin().read_line(&mut response).unwrap();
+        let Some(response) = read_line()? else {
+            break;
+        };
         let response = response.trim().to_lowercase();
         if response == "quit" {
             break;
         }
-        if response.len() != 5 {
-            println!("{}", "Please enter a 5 letter word".red());
+        if response.len() != word_len {
+            println!("{}", format!("Please enter a {} letter word", word_len).red());
             continue;
         }
         if !collection.contains_word(&response) {
@@ -719,51 +855,158 @@ fn play_cows_and_bulls() {
         else {
             compare_print_cb(&word, &response);
         }
-        count = count + 1;
+        count += 1;
     }
     if count > 20 {
-        println!("{} {}", "Loser!".red(), word.to_string().blue());
+        println!("{} {}", "Loser!".red(), word.blue());
     }
+    Ok(())
 }
 
-fn main() {
-    download_file_if_needed("https://www-cs-faculty.stanford.edu/~knuth/sgb-words.txt", "sgb-words.txt");
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() == 1 {
-        solve_wordle();
-    }
-    else if args[1] == "wordle" {
-        play_wordle();
-    }
-    else if args[1] == "soft" {
-        solve_wordle_soft_mode(); 
-    }
-    else if args[1] == "cowsandbulls" {
-        play_cows_and_bulls();
+/// tries to solve Cows and Bulls. The reply to each guess is the number of bulls and the number of
+/// cows, for example `2 1`.
+fn solve_cows_and_bulls(config: &Config) -> Result<(), Error> {
+    let mut solver = CowsAndBullsSolver::new(config.answers()?).with_guesses(config.guesses()?);
+    while let Some(guess) = solver.suggest() {
+        println!("{}", guess.to_string().to_uppercase().green().bold());
+        let (bulls, cows) = loop {
+            let Some(response) = read_line()? else {
+                return Ok(());
+            };
+            let response = response.trim().to_uppercase();
+            if response == "?C" {
+                println!("{} words", solver.candidates().len());
+                continue;
+            }
+            if response == "?L" {
+                for word in solver.candidates().words() {
+                    print!("{} ", word);
+                }
+                println!();
+                continue;
+            }
+            let numbers: Vec<u32> = response.split_whitespace().filter_map(|n| n.parse().ok()).collect();
+            match numbers[..] {
+                [bulls, cows] if bulls.checked_add(cows).is_some_and(|sum| sum as usize <= config.word_len) => break (bulls, cows),
+                _ => println!("{}", "please enter the bulls and the cows, like 2 1".red()),
+            }
+        };
+        if bulls as usize == config.word_len {
+            println!("thank you!");
+            return Ok(());
+        }
+        solver.feedback(&guess, bulls, cows);
     }
-    else if args[1] == "cb" {
-        play_cows_and_bulls();
+    println!("{}", "I give up".red());
+    Ok(())
+}
+
+/// the exit code for an error, after sysexits.h: 64 for bad arguments, 65 for bad input, 66 for a
+/// missing file and 74 for other input or output errors.
+fn exit_code(e: &Error) -> u8 {
+    match e {
+        Error::Invalid(_) => 64,
+        Error::Parse(_) => 65,
+        Error::Io { source, .. } if source.kind() == io::ErrorKind::NotFound => 66,
+        Error::Io { .. } => 74,
     }
-    else if args[1].len() == 5 {
-        solve_worlde_with(&args[1]);
+}
+
+/// lets a closed pipe end the program quietly, as it does other command line tools, rather than
+/// make printing panic: `wordlers bench | head` is fine.
+#[cfg(unix)]
+fn restore_sigpipe() {
+    // SAFETY: nothing else is running yet, and SIG_DFL is a valid handler.
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
     }
-    else if args.len() > 3 {
-        if args[1] == "shapes" {
-            find_shape(&args[2], args[3..].to_vec());
+}
+
+#[cfg(not(unix))]
+fn restore_sigpipe() {}
+
+fn main() -> ExitCode {
+    restore_sigpipe();
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            // --help and --version end up here too, on the standard output.
+            let _ = e.print();
+            return if e.use_stderr() { ExitCode::from(64) } else { ExitCode::SUCCESS };
+        }
+    };
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.to_string().red());
+            ExitCode::from(exit_code(&e))
         }
-    }
-    else {
-        println!("{}", "please enter five letters".red());
     }
 }
 
-#[test]
-fn test_wordle_compare() {
-    assert_eq!(wordle_compare("hello", "henlo"), "GGNGG");
-    assert_eq!(wordle_compare("hello", "hello"), "GGGGG");
-    assert_eq!(wordle_compare("hello", "olleh"), "YYGYY");
-    assert_eq!(wordle_compare("hello", "ollen"), "YYGYN");
-    assert_eq!(wordle_compare("hello", "lllen"), "YNGYN");
-    assert_eq!(wordle_compare("hello", "lllle"), "NNGGY");
+/// the session options of `solve` and `soft`, or where a game is recorded by default.
+fn session_file(args: SessionArgs) -> (PathBuf, bool) {
+    match (args.resume, args.session) {
+        (Some(file), _) => (file, true),
+        (None, Some(file)) => (file, false),
+        (None, None) => (default_session_file(), false),
+    }
 }
 
+fn run(cli: Cli) -> Result<(), Error> {
+    let ListArgs { words, answers, length: word_len, strategy, tree } = cli.lists;
+    let words = match words {
+        Some(file) => WordSource::File(file),
+        None => default_words()?,
+    };
+    let answers = answers
+        .or_else(|| std::env::var_os("WORDLERS_ANSWERS").map(PathBuf::from))
+        .map_or_else(|| words.clone(), WordSource::File);
+    let (session_file, resume) = match &cli.command {
+        None => session_file(SessionArgs { session: None, resume: None }),
+        Some(Command::Solve { session, .. } | Command::Soft { session }) => session_file(session.clone()),
+        Some(_) => (default_session_file(), false),
+    };
+    let config = Config { strategy, word_len, words, answers, session_file, resume, tree };
+    let Some(command) = cli.command else {
+        return solve(&Opening::Solver, &config);
+    };
+    match command {
+        Command::Solve { start: None, .. } => solve(&Opening::Solver, &config),
+        Command::Solve { start: Some(start), .. } => {
+            let start = Word::new(&start.to_lowercase())?;
+            if start.len() != word_len {
+                return Err(Error::Invalid(format!("{} does not have {} letters", start, word_len)));
+            }
+            solve(&Opening::Word(start), &config)
+        }
+        Command::Soft { .. } if word_len != 5 => Err(soft_needs_five()),
+        Command::Soft { .. } => solve(&Opening::Soft, &config),
+        Command::Replay { file, format } => replay_session(&file, format, &config),
+        Command::Play { hard, seed } => play_wordle(&config, hard, seed),
+        Command::Absurdle => play_absurdle(&config),
+        Command::Bench { opening, format } => run_bench(&bench_opening(opening.as_deref(), word_len)?, format, &config),
+        Command::Compare { opening, format } => {
+            compare_strategies(&bench_opening(opening.as_deref(), word_len)?, format, &config)
+        }
+        Command::Tree { start, objective, breadth, save, format } => {
+            build_tree(start.as_deref(), objective, breadth, save.as_deref(), format, &config)
+        }
+        Command::Multi { boards } => solve_multi(boards, &config),
+        Command::Quordle => solve_multi(4, &config),
+        Command::Octordle => solve_multi(8, &config),
+        Command::Sedecordle => solve_multi(16, &config),
+        Command::Cb { seed } => play_cows_and_bulls(&config, seed),
+        Command::CbSolve => solve_cows_and_bulls(&config),
+        Command::Shapes { target, rows, solutions, hard, rank, format } => {
+            let options = ShapeOptions { limit: solutions.0, hard_mode: hard, ranked: rank };
+            find_shape(&target, &rows, &options, format, &config)
+        }
+        Command::Draw { target, text, file, ink, hard, rank } => {
+            let options = ShapeOptions { limit: Some(1), hard_mode: hard, ranked: rank };
+            draw_picture(&target, &text.join(" "), file.as_deref(), ink.into(), &options, &config)
+        }
+        Command::Import => import_share(&config),
+        Command::Infer { format } => infer_shared(format, &config),
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::collection::WordCollection;
+use crate::compare::{pattern_code, pattern_count};
+use crate::word::{Word, MAX_WORD_LEN};
+
+/// the first bytes of a cached matrix file.
+const MAGIC: &[u8; 8] = b"WRDLPAT\0";
+
+/// bumped whenever the layout of the file or the encoding of the patterns changes.
+const VERSION: u32 = 2;
+
+// the patterns of the longest words (3^10 = 59049) must fit in a `u16`.
+const _: () = assert!(pattern_count(MAX_WORD_LEN) <= u16::MAX as usize + 1);
+
+/// the response of every answer to every guess, encoded with [`pattern_code`] in a `u16` each.
+///
+/// Strategies look patterns up here instead of comparing the words over and over. Building it
+/// for the full sgb list takes a while, so it is cached on disk (see [`PatternMatrix::load_or_build`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatrix {
+    guesses: Vec<Word>,
+    answers: Vec<Word>,
+    /// row major: the patterns of the first guess against every answer come first.
+    patterns: Vec<u16>,
+    /// the position of each answer in `answers`.
+    answer_index: HashMap<Word, usize>,
+    /// the position in `answers` of each guess that is also an answer.
+    guess_answers: Vec<Option<usize>>,
+    /// the number of letters in each word.
+    word_len: usize,
+    /// the [`PatternMatrix::key`] of the word lists.
+    key: u64,
+}
+
+impl PatternMatrix {
+    /// works out every pattern, spreading the guesses over all cores.
+    pub fn build(guesses: &WordCollection, answers: &WordCollection) -> PatternMatrix {
+        let key = PatternMatrix::key(guesses, answers);
+        let word_len = guesses.word_len();
+        let guesses = guesses.words().to_vec();
+        let answers = answers.words().to_vec();
+        let mut patterns = vec![0u16; guesses.len() * answers.len()];
+        if !answers.is_empty() {
+            let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+            let rows_per_chunk = guesses.len().div_ceil(threads).max(1);
+            thread::scope(|scope| {
+                for (rows, chunk) in guesses.chunks(rows_per_chunk).zip(patterns.chunks_mut(rows_per_chunk * answers.len())) {
+                    let answers = &answers;
+                    scope.spawn(move || {
+                        for (guess, row) in rows.iter().zip(chunk.chunks_mut(answers.len())) {
+                            for (answer, pattern) in answers.iter().zip(row.iter_mut()) {
+                                *pattern = pattern_code(answer, guess) as u16;
+                            }
+                        }
+                    });
+                }
+            });
+        }
+        PatternMatrix::from_parts(guesses, answers, patterns, word_len, key)
+    }
+
+    fn from_parts(guesses: Vec<Word>, answers: Vec<Word>, patterns: Vec<u16>, word_len: usize, key: u64) -> PatternMatrix {
+        let answer_index: HashMap<Word, usize> = answers.iter().enumerate().map(|(i, w)| (*w, i)).collect();
+        let guess_answers = guesses.iter().map(|w| answer_index.get(w).copied()).collect();
+        PatternMatrix { guesses, answers, patterns, answer_index, guess_answers, word_len, key }
+    }
+
+    pub fn guesses(&self) -> &[Word] {
+        &self.guesses
+    }
+
+    pub fn answers(&self) -> &[Word] {
+        &self.answers
+    }
+
+    /// the position of `word` in the answers.
+    pub fn answer_index(&self, word: &Word) -> Option<usize> {
+        self.answer_index.get(word).copied()
+    }
+
+    /// the patterns of every answer for the guess at position `guess`.
+    pub fn row(&self, guess: usize) -> &[u16] {
+        let len = self.answers.len();
+        &self.patterns[guess * len..(guess + 1) * len]
+    }
+
+    pub fn pattern(&self, guess: usize, answer: usize) -> u16 {
+        self.patterns[guess * self.answers.len() + answer]
+    }
+
+    /// the number of `candidates` (positions in the answers) giving each pattern for `guess`,
+    /// indexed by [`pattern_code`].
+    pub fn buckets(&self, guess: usize, candidates: &[usize]) -> Vec<u32> {
+        let row = self.row(guess);
+        let mut buckets = vec![0u32; pattern_count(self.word_len)];
+        for answer in candidates {
+            buckets[row[*answer] as usize] += 1;
+        }
+        buckets
+    }
+
+    /// the expected information (in bits) gained about the `candidates` by playing the guess at
+    /// position `guess`.
+    pub fn entropy(&self, guess: usize, candidates: &[usize]) -> f64 {
+        self.entropy_with(guess, candidates, &mut vec![0; pattern_count(self.word_len)])
+    }
+
+    /// [`PatternMatrix::entropy`] counting in `buckets`, which must be all zero and is left that
+    /// way, so that it does not have to be cleared for every guess.
+    fn entropy_with(&self, guess: usize, candidates: &[usize], buckets: &mut [u32]) -> f64 {
+        let row = self.row(guess);
+        for answer in candidates {
+            buckets[row[*answer] as usize] += 1;
+        }
+        let total = candidates.len() as f64;
+        let mut entropy = 0.0;
+        for answer in candidates {
+            let count = std::mem::take(&mut buckets[row[*answer] as usize]);
+            if count > 0 {
+                let p = count as f64 / total;
+                entropy -= p * p.log2();
+            }
+        }
+        entropy
+    }
+
+    /// the largest bucket and the sum of the squared buckets of `guess`, counting in `buckets` as
+    /// [`PatternMatrix::entropy_with`] does.
+    fn worst_case_with(&self, guess: usize, candidates: &[usize], buckets: &mut [u32]) -> (u32, u32) {
+        let row = self.row(guess);
+        for answer in candidates {
+            buckets[row[*answer] as usize] += 1;
+        }
+        let (mut largest, mut squares) = (0, 0);
+        for answer in candidates {
+            let count = std::mem::take(&mut buckets[row[*answer] as usize]);
+            largest = largest.max(count);
+            squares += count * count;
+        }
+        (largest, squares)
+    }
+
+    /// the guess with the highest entropy over the `candidates` (positions in the answers), or
+    /// None when there are no candidates. Guesses that are candidates themselves win ties.
+    pub fn max_entropy_guess(&self, candidates: &[usize]) -> Option<Word> {
+        match candidates.len() {
+            0 => return None,
+            1 | 2 => return Some(self.answers[candidates[0]]),
+            _ => {}
+        }
+        let mut is_candidate = vec![false; self.answers.len()];
+        for answer in candidates {
+            is_candidate[*answer] = true;
+        }
+        // no guess can do better than telling every candidate apart.
+        let perfect = (candidates.len() as f64).log2();
+        let mut buckets = vec![0; pattern_count(self.word_len)];
+        let mut best: Option<(usize, f64, bool)> = None;
+        for guess in 0..self.guesses.len() {
+            let score = self.entropy_with(guess, candidates, &mut buckets);
+            let candidate = self.guess_answers[guess].is_some_and(|a| is_candidate[a]);
+            let better = match best {
+                None => true,
+                Some((_, best_score, best_candidate)) =>
+                    score > best_score + 1e-9 || (candidate && !best_candidate && score > best_score - 1e-9),
+            };
+            if better {
+                best = Some((guess, score, candidate));
+                if candidate && score > perfect - 1e-9 {
+                    break;
+                }
+            }
+        }
+        best.map(|(guess, _, _)| self.guesses[guess])
+    }
+
+    /// the guess whose most common response leaves the fewest `candidates` (positions in the
+    /// answers), the fewest left on average breaking ties, or None when there are no candidates.
+    /// Guesses that are candidates themselves win the remaining ties.
+    pub fn minimax_guess(&self, candidates: &[usize]) -> Option<Word> {
+        match candidates.len() {
+            0 => return None,
+            1 | 2 => return Some(self.answers[candidates[0]]),
+            _ => {}
+        }
+        let mut is_candidate = vec![false; self.answers.len()];
+        for answer in candidates {
+            is_candidate[*answer] = true;
+        }
+        // the largest bucket, the sum of the squared buckets and whether the guess is not a
+        // candidate: the smaller the better.
+        let mut buckets = vec![0; pattern_count(self.word_len)];
+        let mut best: Option<(usize, (u32, u32, bool))> = None;
+        for guess in 0..self.guesses.len() {
+            let (largest, squares) = self.worst_case_with(guess, candidates, &mut buckets);
+            let candidate = self.guess_answers[guess].is_some_and(|a| is_candidate[a]);
+            let score = (largest, squares, !candidate);
+            if best.is_none_or(|(_, best_score)| score < best_score) {
+                best = Some((guess, score));
+                // a candidate that tells every candidate apart cannot be beaten.
+                if score == (1, candidates.len() as u32, false) {
+                    break;
+                }
+            }
+        }
+        best.map(|(guess, _)| self.guesses[guess])
+    }
+
+    /// a hash of the word lists, used to tell whether a cached matrix is still valid. It is
+    /// FNV-1a so that it stays the same across builds and platforms.
+    pub fn key(guesses: &WordCollection, answers: &WordCollection) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut add = |b: u8| {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        };
+        add(guesses.word_len() as u8);
+        for list in [guesses, answers] {
+            for word in list.words() {
+                word.letters().iter().for_each(|b| add(*b));
+                add(b'\n');
+            }
+            add(0);
+        }
+        hash
+    }
+
+    /// writes the matrix to `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&VERSION.to_le_bytes())?;
+        file.write_all(&(self.word_len as u32).to_le_bytes())?;
+        file.write_all(&self.key.to_le_bytes())?;
+        file.write_all(&(self.guesses.len() as u32).to_le_bytes())?;
+        file.write_all(&(self.answers.len() as u32).to_le_bytes())?;
+        for pattern in &self.patterns {
+            file.write_all(&pattern.to_le_bytes())?;
+        }
+        file.flush()
+    }
+
+    /// reads the matrix for the given word lists from `path`. Returns None when the file was
+    /// written by another version or for other word lists.
+    pub fn load(path: &Path, guesses: &WordCollection, answers: &WordCollection) -> io::Result<Option<PatternMatrix>> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 8];
+        file.read_exact(&mut magic)?;
+        let mut word = [0u8; 4];
+        let mut read_u32 = |file: &mut BufReader<File>| -> io::Result<u32> {
+            file.read_exact(&mut word)?;
+            Ok(u32::from_le_bytes(word))
+        };
+        let version = read_u32(&mut file)?;
+        let word_len = read_u32(&mut file)?;
+        let mut key = [0u8; 8];
+        file.read_exact(&mut key)?;
+        let key = u64::from_le_bytes(key);
+        let guess_count = read_u32(&mut file)?;
+        let answer_count = read_u32(&mut file)?;
+        if &magic != MAGIC || version != VERSION || word_len as usize != guesses.word_len() ||
+            key != PatternMatrix::key(guesses, answers) ||
+            guess_count as usize != guesses.len() || answer_count as usize != answers.len() {
+            return Ok(None);
+        }
+        let mut bytes = vec![0u8; 2 * guesses.len() * answers.len()];
+        file.read_exact(&mut bytes)?;
+        let patterns = bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect();
+        Ok(Some(PatternMatrix::from_parts(guesses.words().to_vec(), answers.words().to_vec(), patterns, word_len as usize, key)))
+    }
+
+    /// the cached matrix for the word lists, or a freshly built one that is then cached. Caching
+    /// is best effort: a cache that cannot be written only costs the time to build it again.
+    pub fn load_or_build(guesses: &WordCollection, answers: &WordCollection) -> PatternMatrix {
+        let path = PatternMatrix::cache_path(guesses, answers);
+        if let Ok(Some(matrix)) = PatternMatrix::load(&path, guesses, answers) {
+            return matrix;
+        }
+        let matrix = PatternMatrix::build(guesses, answers);
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = matrix.save(&path);
+        matrix
+    }
+
+    /// where the matrix for the word lists is cached: `$XDG_CACHE_HOME/wordlers`, falling back to
+    /// `~/.cache/wordlers` and then the temporary directory.
+    pub fn cache_path(guesses: &WordCollection, answers: &WordCollection) -> PathBuf {
+        let dir = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .unwrap_or_else(std::env::temp_dir);
+        dir.join("wordlers").join(format!("patterns-{:016x}.bin", PatternMatrix::key(guesses, answers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words() -> WordCollection {
+        WordCollection::from_lines(["crane", "slate", "speed", "geese", "llama", "hello"], 5)
+    }
+
+    #[test]
+    fn test_build() {
+        let words = words();
+        let matrix = PatternMatrix::build(&words, &words);
+        for (g, guess) in words.words().iter().enumerate() {
+            for (a, answer) in words.words().iter().enumerate() {
+                assert_eq!(matrix.pattern(g, a) as usize, pattern_code(answer, guess));
+            }
+        }
+        let ten = WordCollection::from_lines(["abcdefghij", "jihgfedcba"], 10);
+        let matrix = PatternMatrix::build(&ten, &ten);
+        assert_eq!(matrix.pattern(0, 0) as usize, pattern_count(10) - 1);
+        assert_eq!(matrix.pattern(1, 0) as usize, pattern_code(&ten.words()[0], &ten.words()[1]));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let words = words();
+        let matrix = PatternMatrix::build(&words, &words);
+        let path = std::env::temp_dir().join(format!("wordlers-test-{}.bin", std::process::id()));
+        matrix.save(&path).unwrap();
+        assert_eq!(PatternMatrix::load(&path, &words, &words).unwrap(), Some(matrix));
+        let other = WordCollection::from_lines(["crane", "slate"], 5);
+        assert_eq!(PatternMatrix::load(&path, &other, &words).unwrap(), None);
+        fs::remove_file(&path).unwrap();
+    }
+}
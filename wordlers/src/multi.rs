@@ -0,0 +1,231 @@
+use crate::collection::WordCollection;
+use crate::error::Error;
+use crate::feedback::Feedback;
+use crate::matrix::PatternMatrix;
+use crate::solver::Solver;
+use crate::word::Word;
+
+/// solves several wordle boards at once (Quordle, Octordle, ...), where every guess is played on
+/// all the boards that are not solved yet.
+#[derive(Debug, Clone)]
+pub struct MultiSolver {
+    /// one solver, with its own state and candidates, per board.
+    boards: Vec<Solver>,
+    /// whether each board has been solved.
+    solved: Vec<bool>,
+    /// the words accepted as guesses.
+    guesses: WordCollection,
+}
+
+impl MultiSolver {
+    /// `count` boards, each of which starts out like `solver`.
+    pub fn new(solver: Solver, count: usize) -> MultiSolver {
+        MultiSolver {
+            guesses: solver.guesses().clone(),
+            boards: vec![solver; count],
+            solved: vec![false; count],
+        }
+    }
+
+    pub fn boards(&self) -> &[Solver] {
+        &self.boards
+    }
+
+    /// the indices of the boards that still need solving.
+    pub fn unsolved(&self) -> Vec<usize> {
+        (0..self.boards.len()).filter(|i| !self.solved[*i]).collect()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.solved.iter().all(|solved| *solved)
+    }
+
+    /// the next word to play on every unsolved board, or None when some board has run out of
+    /// candidates.
+    ///
+    /// The last unsolved board is left to its own solver, with its strategy and decision tree.
+    /// Before that, a board with a single candidate left is finished off first. Otherwise the
+    /// guess is the one with the highest entropy summed over the unsolved boards, with the
+    /// candidates of any board winning ties.
+    pub fn suggest(&self) -> Option<Word> {
+        let unsolved = self.unsolved();
+        if unsolved.iter().any(|i| self.boards[*i].candidates().is_empty()) {
+            return None;
+        }
+        if let [last] = unsolved[..] {
+            return self.boards[last].suggest();
+        }
+        if let Some(i) = unsolved.iter().find(|i| self.boards[**i].candidates().len() == 1) {
+            return Some(self.boards[*i].candidates().words()[0]);
+        }
+        let indexed: Option<Vec<(&PatternMatrix, Vec<usize>)>> = unsolved.iter()
+            .map(|i| self.boards[*i].candidate_indices())
+            .collect();
+        if let Some(indexed) = indexed {
+            return MultiSolver::matrix_suggest(indexed);
+        }
+        // boards that have seen the same responses share their candidates, so score those once.
+        let mut groups: Vec<(&WordCollection, f64)> = Vec::new();
+        for i in &unsolved {
+            let candidates = self.boards[*i].candidates();
+            match groups.iter_mut().find(|(c, _)| c.words() == candidates.words()) {
+                Some(group) => group.1 += 1.0,
+                None => groups.push((candidates, 1.0)),
+            }
+        }
+        let score = |word: &Word| -> f64 {
+            groups.iter().map(|(candidates, weight)| weight * candidates.entropy(word)).sum()
+        };
+        let mut best: Option<(Word, f64)> = None;
+        let words = groups.iter()
+            .flat_map(|(candidates, _)| candidates.words().iter())
+            .chain(self.guesses.words().iter());
+        for word in words {
+            let word_score = score(word);
+            if best.is_none_or(|(_, best_score)| word_score > best_score + 1e-9) {
+                best = Some((*word, word_score));
+            }
+        }
+        best.map(|(word, _)| word)
+    }
+
+    /// [`MultiSolver::suggest`] with the entropy looked up in the pattern matrix the boards share,
+    /// given the positions of the candidates of each unsolved board in it.
+    fn matrix_suggest(indexed: Vec<(&PatternMatrix, Vec<usize>)>) -> Option<Word> {
+        let patterns = indexed.first()?.0;
+        let mut groups: Vec<(Vec<usize>, f64)> = Vec::new();
+        for (_, candidates) in indexed {
+            match groups.iter_mut().find(|(c, _)| *c == candidates) {
+                Some(group) => group.1 += 1.0,
+                None => groups.push((candidates, 1.0)),
+            }
+        }
+        let mut is_candidate = vec![false; patterns.answers().len()];
+        for answer in groups.iter().flat_map(|(candidates, _)| candidates) {
+            is_candidate[*answer] = true;
+        }
+        let mut best: Option<(usize, f64, bool)> = None;
+        for (guess, word) in patterns.guesses().iter().enumerate() {
+            let score: f64 = groups.iter().map(|(candidates, weight)| weight * patterns.entropy(guess, candidates)).sum();
+            let candidate = patterns.answer_index(word).is_some_and(|a| is_candidate[a]);
+            let better = match best {
+                None => true,
+                Some((_, best_score, best_candidate)) =>
+                    score > best_score + 1e-9 || (candidate && !best_candidate && score > best_score - 1e-9),
+            };
+            if better {
+                best = Some((guess, score, candidate));
+            }
+        }
+        best.map(|(guess, _, _)| patterns.guesses()[guess])
+    }
+
+    /// records the response of the given board to the guess. Fails as [`Solver::feedback`] does.
+    pub fn feedback(&mut self, board: usize, guess: &Word, pattern: &Feedback) -> Result<(), Error> {
+        if self.solved[board] {
+            return Ok(());
+        }
+        if pattern.is_solved() && pattern.len() == guess.len() {
+            self.solved[board] = true;
+            Ok(())
+        }
+        else {
+            self.boards[board].feedback(guess, pattern)
+        }
+    }
+
+    /// forgets every response on every board.
+    pub fn reset(&mut self) {
+        for board in self.boards.iter_mut() {
+            board.reset();
+        }
+        self.solved.iter_mut().for_each(|solved| *solved = false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::compare::wordle_compare;
+    use crate::state::WordleState;
+    use crate::strategy::Strategy;
+
+    #[test]
+    fn test_multi_solver() {
+        let words = WordCollection::from_lines(
+            ["crane", "slate", "brine", "shine", "prone", "adieu", "pylon", "crows", "fight"], 5);
+        let targets = ["shine", "pylon", "crows", "adieu"];
+        let mut solver = MultiSolver::new(Solver::new(words), targets.len());
+        for _ in 0..9 {
+            if solver.is_done() {
+                break;
+            }
+            let guess = solver.suggest().unwrap();
+            for board in solver.unsolved() {
+                solver.feedback(board, &guess, &wordle_compare(targets[board], &guess.to_string()).unwrap()).unwrap();
+            }
+        }
+        assert!(solver.is_done());
+    }
+
+    #[test]
+    fn test_multi_solver_with_patterns() {
+        let words = WordCollection::from_lines(
+            ["crane", "slate", "brine", "shine", "prone", "adieu", "pylon", "crows", "fight"], 5);
+        let patterns = Arc::new(PatternMatrix::build(&words, &words));
+        let targets = ["shine", "pylon", "crows", "adieu"];
+        let mut plain = MultiSolver::new(Solver::new(words.clone()), targets.len());
+        let mut fast = MultiSolver::new(Solver::new(words).with_patterns(patterns), targets.len());
+        while !plain.is_done() {
+            let guess = plain.suggest().unwrap();
+            assert_eq!(fast.suggest(), Some(guess));
+            for board in plain.unsolved() {
+                let response = wordle_compare(targets[board], &guess.to_string()).unwrap();
+                plain.feedback(board, &guess, &response).unwrap();
+                fast.feedback(board, &guess, &response).unwrap();
+            }
+        }
+        assert!(fast.is_done());
+    }
+
+    #[test]
+    fn test_solved_board_is_left_alone() {
+        let words = WordCollection::from_lines(["crane", "slate", "brine", "shine", "prone"], 5);
+        let mut solver = MultiSolver::new(Solver::new(words), 2);
+        let crane = Word::new("crane").unwrap();
+        solver.feedback(0, &crane, &wordle_compare("crane", "crane").unwrap()).unwrap();
+        assert_eq!(solver.unsolved(), vec![1]);
+        // a late response for the solved board changes nothing.
+        solver.feedback(0, &crane, &wordle_compare("shine", "crane").unwrap()).unwrap();
+        assert_eq!(solver.unsolved(), vec![1]);
+        assert_eq!(solver.boards()[0].candidates().len(), 5);
+        assert_eq!(solver.boards()[0].state(), &WordleState::new(5));
+    }
+
+    #[test]
+    fn test_single_candidate_first() {
+        let words = WordCollection::from_lines(
+            ["crane", "slate", "brine", "shine", "prone", "adieu", "pylon", "crows", "fight"], 5);
+        let mut solver = MultiSolver::new(Solver::new(words), 3);
+        let crane = Word::new("crane").unwrap();
+        solver.feedback(1, &crane, &wordle_compare("fight", "crane").unwrap()).unwrap();
+        assert_eq!(solver.boards()[1].candidates().len(), 1);
+        assert_eq!(solver.suggest(), Some(Word::new("fight").unwrap()));
+    }
+
+    #[test]
+    fn test_last_board_uses_its_solver() {
+        let answers = WordCollection::from_lines(["bills", "fills", "hills", "kills", "mills", "pills"], 5);
+        let guesses = answers.union(&WordCollection::from_lines(["khamp"], 5));
+        let board = Solver::new(answers).with_guesses(guesses).with_strategy(Strategy::Frequency);
+        let mut solver = MultiSolver::new(board.clone(), 2);
+        let bills = Word::new("bills").unwrap();
+        solver.feedback(0, &bills, &wordle_compare("bills", "bills").unwrap()).unwrap();
+        // the entropy of the boards together would probe with khamp; the frequency strategy
+        // only plays candidates.
+        assert_eq!(solver.suggest(), board.suggest());
+        assert_ne!(solver.suggest(), Some(Word::new("khamp").unwrap()));
+    }
+}
@@ -0,0 +1,36 @@
+use crate::word::Word;
+
+/// the words played in soft mode to find out as many letters as possible.
+pub const SOFT_OPENERS: [&str; 4] = ["adieu", "pylon", "crows", "fight"];
+
+/// how the first guesses of a game are picked, before the solver takes over.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Opening {
+    /// every guess comes from the solver.
+    #[default]
+    Solver,
+    /// the given word is played first.
+    Word(Word),
+    /// the [`SOFT_OPENERS`] are played, one after the other, until at least four letters of the
+    /// target are known.
+    Soft,
+}
+
+impl Opening {
+    /// the word to play at `turn` (counting from zero) when `known` letters have been reported
+    /// green or yellow so far, or None when it is the solver's turn.
+    pub fn opener(&self, turn: usize, known: usize) -> Option<Word> {
+        match self {
+            Opening::Solver => None,
+            Opening::Word(word) => if turn == 0 { Some(*word) } else { None },
+            Opening::Soft => {
+                if known >= 4 {
+                    None
+                }
+                else {
+                    SOFT_OPENERS.get(turn).and_then(|w| Word::new(w).ok())
+                }
+            }
+        }
+    }
+}
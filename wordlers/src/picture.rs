@@ -0,0 +1,256 @@
+use crate::collection::WordCollection;
+use crate::compare::{pattern_code, pattern_count};
+use crate::feedback::{Feedback, Tile};
+use crate::word::Word;
+
+/// the height of a letter of [`FONT`].
+const GLYPH_HEIGHT: usize = 5;
+
+/// a five by five font for drawing text, # marking the ink.
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 39] = [
+    ('A', [".###.", "#...#", "#####", "#...#", "#...#"]),
+    ('B', ["####.", "#...#", "####.", "#...#", "####."]),
+    ('C', [".####", "#....", "#....", "#....", ".####"]),
+    ('D', ["####.", "#...#", "#...#", "#...#", "####."]),
+    ('E', ["#####", "#....", "####.", "#....", "#####"]),
+    ('F', ["#####", "#....", "####.", "#....", "#...."]),
+    ('G', [".####", "#....", "#..##", "#...#", ".###."]),
+    ('H', ["#...#", "#...#", "#####", "#...#", "#...#"]),
+    ('I', ["#####", "..#..", "..#..", "..#..", "#####"]),
+    ('J', ["#####", "...#.", "...#.", "#..#.", ".##.."]),
+    ('K', ["#...#", "#..#.", "###..", "#..#.", "#...#"]),
+    ('L', ["#....", "#....", "#....", "#....", "#####"]),
+    ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#"]),
+    ('N', ["#...#", "##..#", "#.#.#", "#..##", "#...#"]),
+    ('O', [".###.", "#...#", "#...#", "#...#", ".###."]),
+    ('P', ["####.", "#...#", "####.", "#....", "#...."]),
+    ('Q', [".###.", "#...#", "#.#.#", "#..#.", ".##.#"]),
+    ('R', ["####.", "#...#", "####.", "#..#.", "#...#"]),
+    ('S', [".####", "#....", ".###.", "....#", "####."]),
+    ('T', ["#####", "..#..", "..#..", "..#..", "..#.."]),
+    ('U', ["#...#", "#...#", "#...#", "#...#", ".###."]),
+    ('V', ["#...#", "#...#", "#...#", ".#.#.", "..#.."]),
+    ('W', ["#...#", "#...#", "#.#.#", "##.##", "#...#"]),
+    ('X', ["#...#", ".#.#.", "..#..", ".#.#.", "#...#"]),
+    ('Y', ["#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["#####", "...#.", "..#..", ".#...", "#####"]),
+    ('0', [".###.", "#..##", "#.#.#", "##..#", ".###."]),
+    ('1', ["..#..", ".##..", "..#..", "..#..", ".###."]),
+    ('2', [".###.", "#...#", "..##.", ".#...", "#####"]),
+    ('3', ["####.", "....#", ".###.", "....#", "####."]),
+    ('4', ["#..#.", "#..#.", "#####", "...#.", "...#."]),
+    ('5', ["#####", "#....", "####.", "....#", "####."]),
+    ('6', [".###.", "#....", "####.", "#...#", ".###."]),
+    ('7', ["#####", "...#.", "..#..", ".#...", ".#..."]),
+    ('8', [".###.", "#...#", ".###.", "#...#", ".###."]),
+    ('9', [".###.", "#...#", ".####", "....#", ".###."]),
+    (' ', [".....", ".....", ".....", ".....", "....."]),
+    ('!', ["..#..", "..#..", "..#..", ".....", "..#.."]),
+    ('?', [".###.", "#...#", "..##.", ".....", "..#.."]),
+];
+
+/// a picture made of tiles, to be drawn on wordle boards with [`ShapeSearch`](crate::ShapeSearch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    /// the tiles of each row, top first. Rows may have different lengths; the missing tiles are
+    /// gray.
+    rows: Vec<Vec<Tile>>,
+}
+
+impl Picture {
+    pub fn rows(&self) -> &[Vec<Tile>] {
+        &self.rows
+    }
+
+    /// draws `text` with the built in font in `ink`, one character under the other with a blank
+    /// row in between, so that each character fills a board of six rows. Fails on the first
+    /// character the font does not have.
+    pub fn from_text(text: &str, ink: Tile) -> Result<Picture, String> {
+        let mut rows = Vec::new();
+        for (i, c) in text.chars().enumerate() {
+            let glyph = FONT.iter()
+                .find(|(letter, _)| *letter == c.to_ascii_uppercase())
+                .ok_or_else(|| format!("there is no way to draw {}", c))?;
+            if i > 0 {
+                rows.push(Vec::new());
+            }
+            for line in glyph.1 {
+                rows.push(line.chars().map(|c| if c == '#' { ink } else { Tile::Gray }).collect());
+            }
+        }
+        Ok(Picture { rows })
+    }
+
+    /// reads ASCII art: G, # or * is a green tile, Y or + a yellow one, and anything else is
+    /// gray. Emoji squares work too.
+    pub fn from_ascii(art: &str) -> Picture {
+        let rows = art.lines()
+            .map(|line| {
+                line.chars()
+                    .filter(|c| *c != '\u{fe0f}')
+                    .map(|c| match c {
+                        '#' | '*' => Tile::Green,
+                        '+' => Tile::Yellow,
+                        _ => Tile::from_char(c).unwrap_or(Tile::Gray),
+                    })
+                    .collect()
+            })
+            .collect();
+        Picture { rows }
+    }
+
+    /// reads a portable bitmap (a PBM file, plain or raw), with the black pixels in `ink`.
+    pub fn from_pbm(data: &[u8], ink: Tile) -> Result<Picture, String> {
+        // the header is the magic number, the width and the height, separated by whitespace and
+        // comments.
+        let mut header = Vec::new();
+        let mut pos = 0;
+        while header.len() < 3 {
+            while pos < data.len() && (data[pos].is_ascii_whitespace() || data[pos] == b'#') {
+                if data[pos] == b'#' {
+                    while pos < data.len() && data[pos] != b'\n' {
+                        pos += 1;
+                    }
+                }
+                else {
+                    pos += 1;
+                }
+            }
+            let start = pos;
+            while pos < data.len() && !data[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if start == pos {
+                return Err("the bitmap header is cut short".to_string());
+            }
+            header.push(String::from_utf8_lossy(&data[start..pos]).to_string());
+        }
+        let size = |s: &str| s.parse::<usize>().map_err(|_| format!("{} is not a bitmap size", s));
+        let (width, height) = (size(&header[1])?, size(&header[2])?);
+        let bits: Vec<bool> = match header[0].as_str() {
+            "P1" => data[pos..].iter()
+                .filter(|b| **b == b'0' || **b == b'1')
+                .map(|b| *b == b'1')
+                .collect(),
+            "P4" => {
+                // a single whitespace byte separates the header from the packed rows.
+                let row_bytes = width.div_ceil(8);
+                let packed = data.get(pos + 1..).unwrap_or_default();
+                (0..height)
+                    .flat_map(|y| (0..width).map(move |x| (y, x)))
+                    .map(|(y, x)| packed.get(y * row_bytes + x / 8).is_some_and(|b| b & (0x80 >> (x % 8)) != 0))
+                    .collect()
+            }
+            magic => return Err(format!("{} is not a bitmap (P1 or P4)", magic)),
+        };
+        if bits.len() < width * height {
+            return Err(format!("the bitmap has {} pixels, not {}", bits.len(), width * height));
+        }
+        let rows = bits[..width * height].chunks(width.max(1))
+            .map(|row| row.iter().map(|black| if *black { ink } else { Tile::Gray }).collect())
+            .collect();
+        Ok(Picture { rows })
+    }
+
+    /// cuts the picture into boards of `word_len` columns and up to `board_rows` rows, left to
+    /// right and then top to bottom.
+    pub fn boards(&self, word_len: usize, board_rows: usize) -> Vec<Vec<Feedback>> {
+        let width = self.rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        let mut boards = Vec::new();
+        for band in self.rows.chunks(board_rows) {
+            for left in (0..width).step_by(word_len) {
+                let board = band.iter()
+                    .map(|row| {
+                        let tiles: Vec<Tile> = (left..left + word_len)
+                            .map(|x| row.get(x).copied().unwrap_or(Tile::Gray))
+                            .collect();
+                        Feedback::new(&tiles)
+                    })
+                    .collect();
+                boards.push(board);
+            }
+        }
+        boards
+    }
+}
+
+/// how far apart two tiles look: swapping green and yellow keeps the ink, losing or adding it
+/// changes the picture more.
+fn tile_distance(a: Tile, b: Tile) -> usize {
+    match (a, b) {
+        _ if a == b => 0,
+        (Tile::Gray, _) | (_, Tile::Gray) => 2,
+        _ => 1,
+    }
+}
+
+/// the rows of a board that draw `wanted` as well as the `words` can, each played once, when the
+/// target is `target`. A row is kept when enough words give it, and otherwise replaced by the
+/// response that looks the most like it among those some unused word gives. Only the last row may
+/// be all green, since that ends the game. None when there are not enough words.
+pub fn nearest_patterns(target: &Word, wanted: &[Feedback], words: &WordCollection) -> Option<Vec<Feedback>> {
+    if words.word_len() != target.len() || wanted.iter().any(|row| row.len() != target.len()) {
+        return None;
+    }
+    let mut unused = vec![0usize; pattern_count(target.len())];
+    for word in words.words() {
+        unused[pattern_code(target, word)] += 1;
+    }
+    let solved = Feedback::solved(target.len()).code();
+    let mut rows = Vec::new();
+    for (i, row) in wanted.iter().enumerate() {
+        let code = (0..unused.len())
+            .filter(|code| unused[*code] > 0 && (*code != solved || i + 1 == wanted.len()))
+            .min_by_key(|code| {
+                let pattern = Feedback::from_code(*code, target.len());
+                pattern.tiles().iter().zip(row.tiles()).map(|(a, b)| tile_distance(*a, *b)).sum::<usize>()
+            })?;
+        unused[code] -= 1;
+        rows.push(Feedback::from_code(code, target.len()));
+    }
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_boards() {
+        let picture = Picture::from_text("hi", Tile::Green).unwrap();
+        let boards = picture.boards(5, 6);
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].len(), 6);
+        assert_eq!(boards[0][2].to_string(), "GGGGG");
+        assert_eq!(boards[0][5].to_string(), "NNNNN");
+        assert_eq!(boards[1][0].to_string(), "GGGGG");
+        assert!(Picture::from_text("h@", Tile::Green).is_err());
+        // wider than a word: the right half goes on its own board.
+        assert_eq!(Picture::from_ascii("#.+...#\n").boards(5, 6)[1][0].to_string(), "NGNNN");
+    }
+
+    #[test]
+    fn test_pbm() {
+        let plain = Picture::from_pbm(b"P1\n# an L\n2 3\n1 0\n1 0\n1 1\n", Tile::Yellow).unwrap();
+        let raw = Picture::from_pbm(b"P4 2 3\n\x80\x80\xc0", Tile::Yellow).unwrap();
+        assert_eq!(plain, raw);
+        assert_eq!(plain.boards(5, 6)[0][2].to_string(), "YYNNN");
+        assert!(Picture::from_pbm(b"P2 2 3", Tile::Yellow).is_err());
+    }
+
+    #[test]
+    fn test_nearest_patterns() {
+        let words = WordCollection::from_lines(["brine", "shine", "bills", "crane"], 5);
+        let target = Word::new("brine").unwrap();
+        let rows = |rows: &[&str]| rows.iter().map(|row| row.parse().unwrap()).collect::<Vec<Feedback>>();
+        let shine = rows(&["NNGGG"]);
+        assert_eq!(nearest_patterns(&target, &shine, &words), Some(shine));
+        // no word gets YYNNN; bills (GYNNN) is the closest. Only shine gives NNGGG, so the second
+        // one becomes crane's NGNGG.
+        let nearest = nearest_patterns(&target, &rows(&["YYNNN", "NNGGG", "NNGGG"]), &words).unwrap();
+        assert_eq!(nearest, rows(&["GYNNN", "NNGGG", "NGNGG"]));
+        // the game is over after an all green row.
+        assert_eq!(nearest_patterns(&target, &rows(&["GGGGG", "GGGGG"]), &words).unwrap()[0].to_string(), "NNGGG");
+        assert_eq!(nearest_patterns(&target, &rows(&["NNGGG"; 5]), &words), None);
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::feedback::{Feedback, ParseFeedbackError};
+use crate::solver::Solver;
+use crate::word::Word;
+
+/// a line of a session file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Line {
+    guess: String,
+    response: String,
+}
+
+/// the guesses and responses of a game, kept in a file as it goes so that it survives the
+/// program. The file has one JSON object per line:
+///
+/// ```text
+/// {"guess":"crane","response":"NYNNG"}
+/// ```
+#[derive(Debug, Default)]
+pub struct Session {
+    /// every guess with its response, in order.
+    steps: Vec<(Word, Feedback)>,
+    /// where new steps are appended, if anywhere.
+    file: Option<File>,
+    /// the path of `file`.
+    path: Option<PathBuf>,
+}
+
+impl Session {
+    /// a session that is not saved anywhere.
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    /// starts a new session in `path`, replacing whatever was there.
+    pub fn create(path: &Path) -> Result<Session, Error> {
+        Ok(Session {
+            steps: Vec::new(),
+            file: Some(File::create(path).map_err(|e| Error::file(path, e))?),
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// reads the session saved in `path`, to carry on with it. New steps go to the same file.
+    pub fn open(path: &Path) -> Result<Session, Error> {
+        let file_error = |e| Error::file(path, e);
+        let mut steps = Vec::new();
+        for (i, line) in BufReader::new(File::open(path).map_err(file_error)?).lines().enumerate() {
+            let line = line.map_err(file_error)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |e: String| Error::Parse(format!("{} line {}: {}", path.display(), i + 1, e));
+            let Line { guess, response } = serde_json::from_str(&line).map_err(|e| invalid(e.to_string()))?;
+            let response: Feedback = response.parse().map_err(|e: ParseFeedbackError| invalid(e.to_string()))?;
+            let word = Word::new(&guess).map_err(|e| invalid(e.to_string()))?;
+            if word.len() != response.len() {
+                return Err(invalid(format!("{} does not fit the response {}", guess, response)));
+            }
+            steps.push((word, response));
+        }
+        let file = OpenOptions::new().append(true).open(path).map_err(file_error)?;
+        Ok(Session { steps, file: Some(file), path: Some(path.to_path_buf()) })
+    }
+
+    pub fn steps(&self) -> &[(Word, Feedback)] {
+        &self.steps
+    }
+
+    /// adds a step and writes it out straight away.
+    pub fn record(&mut self, guess: &Word, response: &Feedback) -> Result<(), Error> {
+        self.steps.push((*guess, *response));
+        match (self.file.as_mut(), &self.path) {
+            (Some(file), Some(path)) => Session::write_step(file, guess, response).map_err(|e| Error::file(path, e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_step(file: &mut File, guess: &Word, response: &Feedback) -> io::Result<()> {
+        let line = Line { guess: guess.to_string(), response: response.to_string() };
+        writeln!(file, "{}", serde_json::to_string(&line)?)?;
+        file.flush()
+    }
+
+    /// takes the last step back, rewriting the file without it. Returns the step, or None when
+    /// there is none.
+    pub fn undo(&mut self) -> Result<Option<(Word, Feedback)>, Error> {
+        let Some(step) = self.steps.pop() else {
+            return Ok(None);
+        };
+        if let Some(path) = &self.path {
+            let file_error = |e| Error::file(path, e);
+            let mut file = File::create(path).map_err(file_error)?;
+            for (guess, response) in &self.steps {
+                Session::write_step(&mut file, guess, response).map_err(file_error)?;
+            }
+            self.file = Some(file);
+        }
+        Ok(Some(step))
+    }
+
+    /// feeds every step to the solver and returns the number of candidates left after each one.
+    pub fn replay(&self, solver: &mut Solver) -> Result<Vec<usize>, Error> {
+        self.steps.iter()
+            .map(|(guess, response)| {
+                solver.feedback(guess, response)?;
+                Ok(solver.candidates().len())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::WordCollection;
+    use crate::compare::wordle_compare;
+
+    #[test]
+    fn test_save_and_resume() {
+        let path = std::env::temp_dir().join(format!("wordlers-session-{}.jsonl", std::process::id()));
+        let mut session = Session::create(&path).unwrap();
+        for guess in ["crane", "whine"] {
+            session.record(&Word::new(guess).unwrap(), &wordle_compare("shine", guess).unwrap()).unwrap();
+        }
+        let mut resumed = Session::open(&path).unwrap();
+        assert_eq!(resumed.steps(), session.steps());
+        resumed.record(&Word::new("shine").unwrap(), &Feedback::solved(5)).unwrap();
+        assert_eq!(Session::open(&path).unwrap().steps().len(), 3);
+        assert_eq!(resumed.undo().unwrap(), Some((Word::new("shine").unwrap(), Feedback::solved(5))));
+        assert_eq!(Session::open(&path).unwrap().steps(), session.steps());
+
+        let words = WordCollection::from_lines(["crane", "slate", "brine", "shine", "prone", "spine", "swine"], 5);
+        let counts = session.replay(&mut Solver::new(words)).unwrap();
+        assert_eq!(counts, vec![3, 1]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
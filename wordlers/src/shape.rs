@@ -0,0 +1,203 @@
+use crate::collection::WordCollection;
+use crate::compare::{pattern_code, uses_hints};
+use crate::feedback::Feedback;
+use crate::word::Word;
+
+/// looks for guesses that draw a picture on the board: given the target and the response wanted
+/// on every row, it finds a distinct word for each row that gets exactly that response.
+///
+/// The words are taken to be listed from the most common to the least common, as the sgb list
+/// is, and the answers come before the other guesses.
+#[derive(Debug, Clone)]
+pub struct ShapeSearch {
+    /// the word the board is solved with.
+    target: Word,
+    /// the response wanted on each row.
+    rows: Vec<Feedback>,
+    /// the words that may be played, most common first.
+    words: Vec<Word>,
+    /// whether each guess must use every hint the previous rows revealed.
+    hard_mode: bool,
+    /// whether the solutions made of the most common words come first.
+    ranked: bool,
+    /// the most solutions to return, None for all of them.
+    limit: Option<usize>,
+}
+
+impl ShapeSearch {
+    pub fn new(target: Word, rows: Vec<Feedback>, words: &WordCollection) -> ShapeSearch {
+        ShapeSearch {
+            target,
+            rows,
+            words: words.words().to_vec(),
+            hard_mode: false,
+            ranked: false,
+            limit: None,
+        }
+    }
+
+    /// only allows guesses that are valid in hard mode, given the rows before them.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> ShapeSearch {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// returns the solutions whose words are the most common first, rather than the first ones
+    /// found.
+    pub fn with_ranking(mut self, ranked: bool) -> ShapeSearch {
+        self.ranked = ranked;
+        self
+    }
+
+    /// stops after `limit` solutions.
+    pub fn with_limit(mut self, limit: usize) -> ShapeSearch {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// the rows that no word gets as a response, whatever the other rows are.
+    pub fn impossible_rows(&self) -> Vec<Feedback> {
+        self.matches().iter().zip(&self.rows)
+            .filter(|(matches, _)| matches.is_empty())
+            .map(|(_, row)| *row)
+            .collect()
+    }
+
+    /// the positions in `words` of the words that get each row as a response, most common first.
+    fn matches(&self) -> Vec<Vec<usize>> {
+        self.rows.iter()
+            .map(|row| {
+                let code = row.code();
+                (0..self.words.len())
+                    .filter(|i| row.len() == self.target.len() && pattern_code(&self.target, &self.words[*i]) == code)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// every way of drawing the rows with distinct words, or the first [`ShapeSearch::with_limit`]
+    /// of them. Ranked solutions are ordered by the sum of the positions of their words.
+    pub fn solutions(&self) -> Vec<Vec<Word>> {
+        let matches = self.matches();
+        // the cheapest the rows from each one on can be, used to cut ranked searches short.
+        let mut cheapest = vec![0; self.rows.len() + 1];
+        for row in (0..self.rows.len()).rev() {
+            cheapest[row] = cheapest[row + 1] + matches[row].first().copied().unwrap_or(0);
+        }
+        let mut search = Search { shape: self, matches, cheapest, path: Vec::new(), found: Vec::new() };
+        if search.matches.iter().all(|m| !m.is_empty()) && self.limit != Some(0) {
+            search.extend(0);
+        }
+        search.found.into_iter()
+            .map(|(_, path)| path.iter().map(|i| self.words[*i]).collect())
+            .collect()
+    }
+}
+
+/// the state of a [`ShapeSearch::solutions`] run.
+struct Search<'a> {
+    shape: &'a ShapeSearch,
+    matches: Vec<Vec<usize>>,
+    cheapest: Vec<usize>,
+    /// the words picked for the rows so far.
+    path: Vec<usize>,
+    /// the solutions found with their cost, sorted by cost when ranking.
+    found: Vec<(usize, Vec<usize>)>,
+}
+
+impl Search<'_> {
+    /// the cost above which a ranked solution cannot make it into the results any more.
+    fn bound(&self) -> usize {
+        match self.shape.limit {
+            Some(limit) if self.shape.ranked && self.found.len() >= limit => self.found[limit - 1].0,
+            _ => usize::MAX,
+        }
+    }
+
+    /// tries every word for `row` and the rows after it. Returns false once the search is over.
+    fn extend(&mut self, row: usize) -> bool {
+        if row == self.shape.rows.len() {
+            let cost = self.path.iter().sum();
+            let position = if self.shape.ranked {
+                self.found.partition_point(|(c, _)| *c <= cost)
+            } else {
+                self.found.len()
+            };
+            self.found.insert(position, (cost, self.path.clone()));
+            return match self.shape.limit {
+                Some(limit) if self.shape.ranked => {
+                    self.found.truncate(limit);
+                    true
+                }
+                Some(limit) => self.found.len() < limit,
+                None => true,
+            };
+        }
+        let cost: usize = self.path.iter().sum();
+        for k in 0..self.matches[row].len() {
+            let word = self.matches[row][k];
+            if cost + word + self.cheapest[row + 1] >= self.bound() {
+                // the matches are sorted, so the rest can only cost more.
+                break;
+            }
+            if self.path.contains(&word) || (self.shape.hard_mode && !self.keeps_hints(row, word)) {
+                continue;
+            }
+            self.path.push(word);
+            let more = self.extend(row + 1);
+            self.path.pop();
+            if !more {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// [`uses_hints`] for every row before `row`.
+    fn keeps_hints(&self, row: usize, word: usize) -> bool {
+        let word = &self.shape.words[word];
+        self.path.iter().zip(&self.shape.rows[..row])
+            .all(|(guess, response)| uses_hints(word, &self.shape.words[*guess], response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compare::wordle_compare;
+
+    fn words() -> WordCollection {
+        WordCollection::from_lines(["brine", "bills", "hills", "crane", "shine", "prone", "spine", "swine"], 5)
+    }
+
+    #[test]
+    fn test_all_solutions() {
+        let rows = vec!["NNGGG".parse().unwrap(), "NNGGG".parse().unwrap(), Feedback::solved(5)];
+        let search = ShapeSearch::new(Word::new("brine").unwrap(), rows.clone(), &words());
+        let solutions = search.solutions();
+        // shine, spine and swine in any order for the first two rows.
+        assert_eq!(solutions.len(), 6);
+        for solution in &solutions {
+            for (word, row) in solution.iter().zip(&rows) {
+                assert_eq!(wordle_compare("brine", &word.to_string()).unwrap(), *row);
+            }
+        }
+        assert_eq!(search.clone().with_limit(2).solutions().len(), 2);
+        let best = search.with_ranking(true).with_limit(1).solutions();
+        assert_eq!(best, vec![vec![Word::new("shine").unwrap(), Word::new("spine").unwrap(), Word::new("brine").unwrap()]]);
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        // crane and prone keep each other's greens, in either order.
+        let rows = vec![wordle_compare("brine", "crane").unwrap(), wordle_compare("brine", "prone").unwrap()];
+        assert_eq!(ShapeSearch::new(Word::new("brine").unwrap(), rows, &words()).with_hard_mode(true).solutions().len(), 2);
+        // bills shows a green B that shine drops.
+        let rows = vec![wordle_compare("brine", "bills").unwrap(), wordle_compare("brine", "shine").unwrap()];
+        let search = ShapeSearch::new(Word::new("brine").unwrap(), rows, &words());
+        assert!(!search.solutions().is_empty());
+        assert!(search.with_hard_mode(true).solutions().is_empty());
+        let impossible = ShapeSearch::new(Word::new("brine").unwrap(), vec!["YYYYY".parse().unwrap()], &words());
+        assert_eq!(impossible.impossible_rows().len(), 1);
+    }
+}
@@ -0,0 +1,177 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::feedback::{Feedback, ParseFeedbackError};
+
+/// a result as wordle shares it:
+///
+/// ```text
+/// Wordle 1,234 4/6*
+///
+/// ⬛🟨⬛⬛⬛
+/// 🟩⬛🟨⬛⬛
+/// 🟩🟩🟩⬛🟩
+/// 🟩🟩🟩🟩🟩
+/// ```
+///
+/// The header is optional. The rows only tell the colours, not the guesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareGrid {
+    /// the name of the game and the number of the puzzle, as in "Wordle 1,234".
+    pub title: Option<String>,
+    /// the number of guesses it took, None when the player failed (X/6) or there is no header.
+    pub score: Option<usize>,
+    /// whether the header has the hard mode star.
+    pub hard_mode: bool,
+    /// the response to every guess, in order.
+    pub rows: Vec<Feedback>,
+}
+
+/// why a share block could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseShareError {
+    /// there was no row of squares.
+    NoRows,
+    /// a line after the rows started is not a row.
+    BadRow(String, ParseFeedbackError),
+    /// the rows do not all have the same number of squares.
+    UnevenRows,
+}
+
+impl fmt::Display for ParseShareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseShareError::NoRows => write!(f, "there are no rows of squares"),
+            ParseShareError::BadRow(line, e) => write!(f, "{}: {} is not a square", line, e.invalid),
+            ParseShareError::UnevenRows => write!(f, "the rows do not all have the same length"),
+        }
+    }
+}
+
+impl Error for ParseShareError {}
+
+impl ShareGrid {
+    /// the number of letters in each word.
+    pub fn word_len(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    /// whether the last row is all green.
+    pub fn is_solved(&self) -> bool {
+        self.rows.last().is_some_and(|row| row.is_solved())
+    }
+
+    /// reads several share blocks pasted one after the other. A block ends at its solved row, at
+    /// a blank line or at a header following its rows.
+    pub fn parse_all(s: &str) -> Result<Vec<ShareGrid>, ParseShareError> {
+        let mut grids = Vec::new();
+        let mut block = String::new();
+        let mut rows = 0;
+        for line in s.lines() {
+            let row = Feedback::from_squares(line).ok().filter(|row| !row.is_empty());
+            if rows > 0 && row.is_none() {
+                grids.push(block.parse()?);
+                block.clear();
+                rows = 0;
+            }
+            block.push_str(line);
+            block.push('\n');
+            if let Some(row) = row {
+                rows += 1;
+                if row.is_solved() {
+                    grids.push(block.parse()?);
+                    block.clear();
+                    rows = 0;
+                }
+            }
+        }
+        if rows > 0 {
+            grids.push(block.parse()?);
+        }
+        Ok(grids)
+    }
+
+    /// reads the score part of the header ("4/6", "X/6" or "4/6*").
+    fn parse_score(token: &str) -> Option<(Option<usize>, bool)> {
+        let (score, hard_mode) = match token.strip_suffix('*') {
+            Some(score) => (score, true),
+            None => (token, false),
+        };
+        let (guesses, _) = score.split_once('/')?;
+        match guesses {
+            "X" | "x" => Some((None, hard_mode)),
+            _ => Some((Some(guesses.parse().ok()?), hard_mode)),
+        }
+    }
+}
+
+impl FromStr for ShareGrid {
+    type Err = ParseShareError;
+
+    fn from_str(s: &str) -> Result<ShareGrid, ParseShareError> {
+        let mut grid = ShareGrid { title: None, score: None, hard_mode: false, rows: Vec::new() };
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            match Feedback::from_squares(line) {
+                Ok(row) => grid.rows.push(row),
+                Err(e) if !grid.rows.is_empty() => return Err(ParseShareError::BadRow(line.to_string(), e)),
+                Err(_) => {
+                    // anything before the rows is the header, as far as we can make sense of it.
+                    let mut tokens: Vec<&str> = line.split_whitespace().collect();
+                    if let Some((score, hard_mode)) = tokens.last().and_then(|t| ShareGrid::parse_score(t)) {
+                        grid.score = score;
+                        grid.hard_mode = hard_mode;
+                        tokens.pop();
+                    }
+                    if !tokens.is_empty() {
+                        grid.title = Some(tokens.join(" "));
+                    }
+                }
+            }
+        }
+        if grid.rows.is_empty() {
+            return Err(ParseShareError::NoRows);
+        }
+        if grid.rows.iter().any(|row| row.len() != grid.word_len()) {
+            return Err(ParseShareError::UnevenRows);
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_share() {
+        let grid: ShareGrid = "Wordle 1,234 4/6*\n\n⬛🟨⬛⬛⬛\n🟩⬜🟨⬛⬛\n🟧🟧🟧⬛🟧\n🟧🟧🟧🟧🟧\n".parse().unwrap();
+        assert_eq!(grid.title.as_deref(), Some("Wordle 1,234"));
+        assert_eq!(grid.score, Some(4));
+        assert!(grid.hard_mode);
+        assert_eq!(grid.rows.len(), 4);
+        assert_eq!(grid.rows[1].to_string(), "GNYNN");
+        assert!(grid.is_solved());
+
+        let grid: ShareGrid = "Wordle 1,235 X/6\n🟦⬛⬛⬛⬛".parse().unwrap();
+        assert_eq!(grid.score, None);
+        assert!(!grid.is_solved());
+        assert_eq!("Wordle 1,236 3/6".parse::<ShareGrid>(), Err(ParseShareError::NoRows));
+        assert_eq!("🟩🟩\n🟩🟩🟩".parse::<ShareGrid>(), Err(ParseShareError::UnevenRows));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let text = "Wordle 1,234 2/6\n\n⬛🟨⬛⬛⬛\n🟩🟩🟩🟩🟩\nWordle 1,234 1/6\n🟩🟩🟩🟩🟩\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩\n\n";
+        let grids = ShareGrid::parse_all(text).unwrap();
+        assert_eq!(grids.len(), 3);
+        assert_eq!(grids[1].score, Some(1));
+        assert_eq!(grids[2].title, None);
+        assert_eq!(grids[2].rows.len(), 2);
+
+        // chat around the blocks is not mistaken for rows.
+        let grids = ShareGrid::parse_all("gg\nby\n⬛🟨⬛⬛⬛\n🟩🟩🟩🟩🟩\n").unwrap();
+        assert_eq!(grids.len(), 1);
+        assert_eq!(grids[0].rows.len(), 2);
+    }
+}
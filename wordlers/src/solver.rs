@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use crate::collection::WordCollection;
+use crate::error::Error;
+use crate::feedback::Feedback;
+use crate::matrix::PatternMatrix;
+use crate::state::WordleState;
+use crate::strategy::Strategy;
+use crate::tree::DecisionTree;
+use crate::word::Word;
+
+/// an interactive wordle solver. It remembers the possible answers, the words it may guess, the
+/// constraints learnt so far and the answers that still satisfy them.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    /// the full list of possible answers the solver started with.
+    answers: WordCollection,
+    /// the words accepted as guesses. Strategies that probe may pick any of these.
+    guesses: WordCollection,
+    /// the words consistent with every response seen so far.
+    candidates: WordCollection,
+    /// the constraints learnt from the responses.
+    state: WordleState,
+    /// how the next guess is picked.
+    strategy: Strategy,
+    /// the patterns of the answers for every guess, when they have been worked out.
+    patterns: Option<Arc<PatternMatrix>>,
+    /// the decision tree to follow, for as long as the game stays on it.
+    tree: Option<Arc<DecisionTree>>,
+    /// the guesses and responses so far.
+    steps: Vec<(Word, Feedback)>,
+    /// the state and candidates before each response, to take responses back.
+    history: Vec<(WordleState, WordCollection)>,
+}
+
+impl Solver {
+    /// a solver where the answers are also the only words allowed as guesses.
+    pub fn new(answers: WordCollection) -> Solver {
+        Solver {
+            candidates: answers.clone(),
+            state: WordleState::new(answers.word_len()),
+            guesses: answers.clone(),
+            answers,
+            strategy: Strategy::default(),
+            patterns: None,
+            tree: None,
+            steps: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// allows the solver to guess any of the given words, on top of the answers.
+    pub fn with_guesses(mut self, guesses: WordCollection) -> Solver {
+        self.guesses = guesses;
+        self
+    }
+
+    pub fn with_strategy(mut self, strategy: Strategy) -> Solver {
+        self.strategy = strategy;
+        self
+    }
+
+    /// lets the strategies look patterns up instead of working them out. The matrix must have
+    /// been built for the same guesses and answers as the solver.
+    pub fn with_patterns(mut self, patterns: Arc<PatternMatrix>) -> Solver {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// plays the guesses of the tree. Once a guess or a response leaves it, the strategy takes
+    /// over.
+    pub fn with_tree(mut self, tree: Arc<DecisionTree>) -> Solver {
+        self.tree = Some(tree);
+        self
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// the next word to guess, or None when no word satisfies the responses so far.
+    pub fn suggest(&self) -> Option<Word> {
+        if let Some(guess) = self.tree.as_ref().and_then(|tree| tree.suggest(&self.steps)) {
+            return Some(guess);
+        }
+        match self.strategy {
+            Strategy::Frequency => self.candidates.get_best_word(),
+            Strategy::Entropy => match self.candidate_indices() {
+                Some((patterns, candidates)) => patterns.max_entropy_guess(&candidates),
+                None => self.candidates.get_max_entropy_word(&self.guesses),
+            },
+            Strategy::Minimax => match self.candidate_indices() {
+                Some((patterns, candidates)) => patterns.minimax_guess(&candidates),
+                None => self.candidates.get_minimax_word(&self.guesses),
+            },
+        }
+    }
+
+    /// the pattern matrix with the positions of the candidates in it, if there is a matrix that
+    /// knows every candidate.
+    pub(crate) fn candidate_indices(&self) -> Option<(&PatternMatrix, Vec<usize>)> {
+        let patterns = self.patterns.as_deref()?;
+        let candidates = self.candidates.words().iter()
+            .map(|word| patterns.answer_index(word))
+            .collect::<Option<Vec<usize>>>()?;
+        Some((patterns, candidates))
+    }
+
+    /// records the wordle response for the given guess and narrows down the candidates. Fails,
+    /// recording nothing, when the guess or the response is not as long as the words.
+    pub fn feedback(&mut self, guess: &Word, pattern: &Feedback) -> Result<(), Error> {
+        let state = self.state.clone();
+        self.state.update(guess, pattern)?;
+        self.history.push((state, self.candidates.clone()));
+        self.steps.push((*guess, *pattern));
+        self.candidates = self.candidates.filter(&self.state);
+        Ok(())
+    }
+
+    /// the words that are still possible answers.
+    pub fn candidates(&self) -> &WordCollection {
+        &self.candidates
+    }
+
+    pub fn state(&self) -> &WordleState {
+        &self.state
+    }
+
+    /// the words accepted as guesses.
+    pub fn guesses(&self) -> &WordCollection {
+        &self.guesses
+    }
+
+    /// takes the last response back. Returns false when there is none.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some((state, candidates)) => {
+                self.state = state;
+                self.candidates = candidates;
+                self.steps.pop();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// forgets every response and starts over with the full answer list.
+    pub fn reset(&mut self) {
+        self.candidates = self.answers.clone();
+        self.state = WordleState::new(self.answers.word_len());
+        self.steps.clear();
+        self.history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compare::wordle_compare;
+    use crate::tree::TreeSearch;
+
+    #[test]
+    fn test_solver_finds_target() {
+        for strategy in Strategy::ALL {
+            let words = WordCollection::from_lines(["crane", "slate", "brine", "shine", "prone"], 5);
+            let mut solver = Solver::new(words).with_strategy(strategy);
+            let target = "shine";
+            let found = (0..6).any(|_| {
+                let guess = solver.suggest().unwrap();
+                solver.feedback(&guess, &wordle_compare(target, &guess.to_string()).unwrap()).unwrap();
+                guess.to_string() == target
+            });
+            assert!(found, "{} did not find {}", strategy, target);
+        }
+    }
+
+    #[test]
+    fn test_solver_probes_with_guesses() {
+        let answers = WordCollection::from_lines(["bills", "fills", "hills", "kills", "mills", "pills"], 5);
+        let guesses = WordCollection::from_lines(["bills", "fills", "hills", "kills", "mills", "pills", "khamp"], 5);
+        for strategy in [Strategy::Entropy, Strategy::Minimax] {
+            let solver = Solver::new(answers.clone()).with_guesses(guesses.clone()).with_strategy(strategy);
+            assert_eq!(solver.suggest(), Some(Word::new("khamp").unwrap()), "{}", strategy);
+            assert!(solver.candidates().words().iter().all(|w| w.to_string() != "khamp"));
+        }
+    }
+
+    #[test]
+    fn test_solver_follows_tree() {
+        let answers = WordCollection::from_lines(["bills", "fills", "hills", "kills", "mills", "pills"], 5);
+        let guesses = answers.union(&WordCollection::from_lines(["khamp"], 5));
+        let patterns = PatternMatrix::build(&guesses, &answers);
+        let tree = Arc::new(TreeSearch::new(&patterns).build().unwrap());
+        let mut solver = Solver::new(answers).with_guesses(guesses).with_tree(tree);
+        let khamp = Word::new("khamp").unwrap();
+        assert_eq!(solver.suggest(), Some(khamp));
+        solver.feedback(&khamp, &wordle_compare("fills", "khamp").unwrap()).unwrap();
+        assert_eq!(solver.suggest(), Some(Word::new("bills").unwrap()));
+        // off the tree, the strategy takes over.
+        solver.undo();
+        let mills = Word::new("mills").unwrap();
+        solver.feedback(&mills, &wordle_compare("fills", "mills").unwrap()).unwrap();
+        assert_eq!(solver.suggest(), solver.candidates().get_best_word());
+    }
+
+    #[test]
+    fn test_solver_with_patterns() {
+        let words = WordCollection::from_lines(
+            ["crane", "slate", "brine", "shine", "prone", "adieu", "pylon", "crows", "fight"], 5);
+        let patterns = Arc::new(PatternMatrix::build(&words, &words));
+        for strategy in [Strategy::Entropy, Strategy::Minimax] {
+            let mut plain = Solver::new(words.clone()).with_strategy(strategy);
+            let mut fast = Solver::new(words.clone()).with_strategy(strategy).with_patterns(patterns.clone());
+            for target in ["pylon", "fight", "crane"] {
+                plain.reset();
+                fast.reset();
+                for _ in 0..3 {
+                    let guess = plain.suggest().unwrap();
+                    assert_eq!(fast.suggest(), Some(guess), "{}", strategy);
+                    let response = wordle_compare(target, &guess.to_string()).unwrap();
+                    plain.feedback(&guess, &response).unwrap();
+                    fast.feedback(&guess, &response).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solver_reset() {
+        let words = WordCollection::from_lines(["crane", "build", "motif"], 5);
+        let mut solver = Solver::new(words);
+        solver.feedback(&Word::new("crane").unwrap(), &"NNNNN".parse().unwrap()).unwrap();
+        assert_eq!(solver.candidates().len(), 2);
+        solver.reset();
+        assert_eq!(solver.candidates().len(), 3);
+    }
+
+    #[test]
+    fn test_solver_undo() {
+        let words = WordCollection::from_lines(["crane", "build", "motif", "shine"], 5);
+        let mut solver = Solver::new(words);
+        let start = solver.state().clone();
+        solver.feedback(&Word::new("crane").unwrap(), &"NNNNN".parse().unwrap()).unwrap();
+        solver.feedback(&Word::new("build").unwrap(), &"NNYNN".parse().unwrap()).unwrap();
+        assert_eq!(solver.candidates().len(), 1);
+        assert!(solver.undo());
+        assert_eq!(solver.candidates().len(), 2);
+        assert!(solver.undo());
+        assert_eq!(solver.candidates().len(), 4);
+        assert_eq!(solver.state(), &start);
+        assert!(!solver.undo());
+    }
+
+    #[test]
+    fn test_feedback_checks_lengths() {
+        let words = WordCollection::from_lines(["crane", "build", "motif"], 5);
+        let mut solver = Solver::new(words);
+        let start = solver.state().clone();
+        assert!(solver.feedback(&Word::new("cranes").unwrap(), &"NNNNNN".parse().unwrap()).is_err());
+        assert!(solver.feedback(&Word::new("crane").unwrap(), &"NNNN".parse().unwrap()).is_err());
+        assert_eq!(solver.state(), &start);
+        assert_eq!(solver.candidates().len(), 3);
+        assert!(!solver.undo());
+    }
+}
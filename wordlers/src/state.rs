@@ -0,0 +1,174 @@
+use crate::charset::CharSet;
+use crate::error::Error;
+use crate::feedback::{Feedback, Tile};
+use crate::word::Word;
+
+/// The constraints on the words. Each positional constraint is represented as a set of
+/// characters, and each letter has a lower and upper bound on the number of times it appears.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordleState {
+    /// the characters allowed at each position.
+    positions: Vec<CharSet>,
+    /// the least number of times each letter is known to appear in the target word.
+    min_counts: [u8; 26],
+    /// the most number of times each letter may appear in the target word. This is exact once a
+    /// response has marked a copy of the letter N.
+    max_counts: [u8; 26],
+}
+
+impl WordleState {
+    /// no constraints yet on words with `word_len` letters.
+    pub fn new(word_len: usize) -> WordleState {
+        WordleState {
+            positions: vec![CharSet::new_full(); word_len],
+            min_counts: [0; 26],
+            max_counts: [word_len as u8; 26],
+        }
+    }
+
+    pub fn is_allowed(&self, word: &Word) -> bool {
+        if word.len() != self.positions.len() ||
+            !self.positions.iter().zip(word.chars()).all(|(allowed, c)| allowed.contains(c)) {
+            return false;
+        }
+        let mut counts = [0u8; 26];
+        for b in word.letters() {
+            counts[(b - b'a') as usize] += 1;
+        }
+        counts.iter().enumerate()
+            .all(|(i, count)| self.min_counts[i] <= *count && *count <= self.max_counts[i])
+    }
+
+    /// gets called when we learn that a letter is not in the target word at all.
+    fn remove_letter(&mut self, c: char) {
+        for allowed in self.positions.iter_mut() {
+            allowed.remove(c);
+        }
+    }
+
+    /// once we got a new word and the response from wordle, update the state.
+    ///
+    /// A gray letter only rules out that letter everywhere when no other copy of it in the
+    /// guess was marked G or Y. Otherwise it tells us the exact number of times the letter
+    /// appears, which is the number of its copies marked G or Y.
+    ///
+    /// Fails, leaving the state as it was, when the word or the response is not as long as the
+    /// words.
+    pub fn update(&mut self, word: &Word, response: &Feedback) -> Result<(), Error> {
+        if word.len() != self.positions.len() || response.len() != self.positions.len() {
+            return Err(Error::Invalid(format!("{} and {} do not fit {} letter words", word, response, self.positions.len())));
+        }
+        let mut found = [0u8; 26];
+        let mut missed = [false; 26];
+        for (i, (c, mark)) in word.chars().zip(response.tiles()).enumerate() {
+            let letter = c as usize - 'a' as usize;
+            match mark {
+                Tile::Green => {
+                    self.positions[i].remove_others(c);
+                    found[letter] += 1;
+                }
+                Tile::Yellow => {
+                    self.positions[i].remove(c);
+                    found[letter] += 1;
+                }
+                Tile::Gray => {
+                    self.positions[i].remove(c);
+                    missed[letter] = true;
+                }
+            }
+        }
+        for c in word.chars() {
+            let letter = c as usize - 'a' as usize;
+            self.min_counts[letter] = self.min_counts[letter].max(found[letter]);
+            if missed[letter] {
+                self.max_counts[letter] = self.max_counts[letter].min(found[letter]);
+                if found[letter] == 0 {
+                    self.remove_letter(c);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// false when no word can meet the constraints, whatever the word list, which means that the
+    /// responses contradict each other.
+    pub fn is_satisfiable(&self) -> bool {
+        let places = |letter: usize| {
+            let c = (b'a' + letter as u8) as char;
+            self.positions.iter().filter(|allowed| allowed.contains(c)).count()
+        };
+        self.positions.iter().all(|allowed| !allowed.is_empty()) &&
+            (0..26).all(|i| self.min_counts[i] <= self.max_counts[i] && self.min_counts[i] as usize <= places(i)) &&
+            self.min_counts.iter().map(|count| *count as usize).sum::<usize>() <= self.positions.len()
+    }
+
+    pub fn is_usable(&self, c: char) -> bool {
+        self.positions.iter().any(|allowed| allowed.contains(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compare::wordle_compare;
+
+    const WORDS: [&str; 20] = [
+        "speed", "spend", "geese", "eerie", "sheep", "erase", "llama", "allay", "label",
+        "lolly", "hello", "lever", "crane", "stats", "moon", "noon", "nook", "banana", "ananas",
+        "bandana",
+    ];
+
+    #[test]
+    fn test_update_keeps_target() {
+        let mut state = WordleState::new(5);
+        state.update(&Word::new("geese").unwrap(), &wordle_compare("speed", "geese").unwrap()).unwrap();
+        assert!(state.is_allowed(&Word::new("speed").unwrap()));
+        let mut state = WordleState::new(5);
+        state.update(&Word::new("allay").unwrap(), &wordle_compare("llama", "allay").unwrap()).unwrap();
+        assert!(state.is_allowed(&Word::new("llama").unwrap()));
+    }
+
+    #[test]
+    fn test_update_counts_letters() {
+        // one E is green, one yellow and one gray: there are exactly two Es.
+        let mut state = WordleState::new(5);
+        state.update(&Word::new("geese").unwrap(), &"NYGYN".parse().unwrap()).unwrap();
+        assert!(state.is_allowed(&Word::new("sheep").unwrap()));
+        assert!(state.is_allowed(&Word::new("speed").unwrap()));
+        assert!(!state.is_allowed(&Word::new("spend").unwrap()));
+        assert!(!state.is_allowed(&Word::new("sleee").unwrap()));
+        assert!(state.is_usable('e'));
+        assert!(!state.is_usable('g'));
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let mut state = WordleState::new(5);
+        state.update(&Word::new("crane").unwrap(), &"GNNNN".parse().unwrap()).unwrap();
+        assert!(state.is_satisfiable());
+        // the C was green, so it cannot be gray at the same place.
+        state.update(&Word::new("cloud").unwrap(), &"NNNNN".parse().unwrap()).unwrap();
+        assert!(!state.is_satisfiable());
+        let mut state = WordleState::new(5);
+        state.update(&Word::new("geese").unwrap(), &"YNNNN".parse().unwrap()).unwrap();
+        state.update(&Word::new("doing").unwrap(), &"NNNNN".parse().unwrap()).unwrap();
+        assert!(!state.is_satisfiable());
+    }
+
+    /// after a single guess, the state must allow exactly those words that would have given the
+    /// same response.
+    #[test]
+    fn test_update_matches_compare() {
+        for guess in WORDS {
+            for target in WORDS.iter().filter(|w| w.len() == guess.len()) {
+                let response = wordle_compare(target, guess).unwrap();
+                let mut state = WordleState::new(guess.len());
+                state.update(&Word::new(guess).unwrap(), &response).unwrap();
+                for word in WORDS.iter().filter(|w| w.len() == guess.len()) {
+                    assert_eq!(state.is_allowed(&Word::new(word).unwrap()), wordle_compare(word, guess).unwrap() == response,
+                               "guess {} response {} word {}", guess, response, word);
+                }
+            }
+        }
+    }
+}
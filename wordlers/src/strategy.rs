@@ -0,0 +1,50 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// how the solver picks the next guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// ranks the candidates by how common their letters are (see
+    /// [`WordCollection::get_best_word`](crate::WordCollection::get_best_word)).
+    #[default]
+    Frequency,
+    /// picks the word, from the whole list, whose responses carry the most information about the
+    /// remaining candidates.
+    Entropy,
+    /// picks the word, from the whole list, whose most common response leaves the fewest
+    /// candidates: the best worst case rather than the best average.
+    Minimax,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Frequency, Strategy::Entropy, Strategy::Minimax];
+
+    /// whether the strategy benefits from a [`PatternMatrix`](crate::PatternMatrix).
+    pub fn uses_patterns(&self) -> bool {
+        *self != Strategy::Frequency
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Strategy::Frequency => "frequency",
+            Strategy::Entropy => "entropy",
+            Strategy::Minimax => "minimax",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Strategy, String> {
+        match s.to_lowercase().as_str() {
+            "frequency" | "freq" => Ok(Strategy::Frequency),
+            "entropy" => Ok(Strategy::Entropy),
+            "minimax" => Ok(Strategy::Minimax),
+            _ => Err(format!("unknown strategy {}", s)),
+        }
+    }
+}
@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::bench::GIVE_UP_AFTER;
+use crate::compare::pattern_count;
+use crate::error::Error;
+use crate::feedback::{Feedback, ParseFeedbackError};
+use crate::matrix::PatternMatrix;
+use crate::word::Word;
+
+/// stands for a set of candidates that cannot be solved in the guesses left.
+const INFEASIBLE: u32 = u32::MAX;
+
+/// what an optimal decision tree minimizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Objective {
+    /// the number of guesses summed over every answer, that is the average.
+    #[default]
+    Total,
+    /// the most guesses any answer takes, then the total.
+    Most,
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Objective::Total => "total",
+            Objective::Most => "most",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Objective, String> {
+        match s.to_lowercase().as_str() {
+            "total" | "average" => Ok(Objective::Total),
+            "most" | "max" => Ok(Objective::Most),
+            _ => Err(format!("unknown objective {}", s)),
+        }
+    }
+}
+
+/// a state of the game: the guess to play, and where to go for each response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    guess: Word,
+    /// whether the guess is one of the candidates, so that the game may end here.
+    solves: bool,
+    /// the next state for each response but the solved one.
+    branches: Vec<(Feedback, Node)>,
+}
+
+impl Node {
+    /// the number of answers that reach this node, the guesses they take from here on in total,
+    /// and the most guesses any of them takes from here on.
+    fn stats(&self) -> (usize, usize, usize) {
+        let mut answers = self.solves as usize;
+        let mut total = 0;
+        let mut most = self.solves as usize;
+        for (_, node) in &self.branches {
+            let (a, t, m) = node.stats();
+            answers += a;
+            total += t;
+            most = most.max(m + 1);
+        }
+        (answers, total + answers, most)
+    }
+
+    fn write(&self, out: &mut impl Write, response: Option<&Feedback>) -> io::Result<()> {
+        if let Some(response) = response {
+            write!(out, "{} ", response)?;
+        }
+        writeln!(out, "{} {} {}", self.guess, self.branches.len(), self.stats().0)?;
+        for (response, node) in &self.branches {
+            node.write(out, Some(response))?;
+        }
+        Ok(())
+    }
+
+    /// reads the node on the next line and the nodes below it. `response` is whether the line
+    /// starts with the response that leads to the node.
+    fn read(lines: &mut impl Iterator<Item = (usize, String)>, response: bool) -> Result<(Option<Feedback>, Node), (usize, String)> {
+        let (n, line) = lines.next().ok_or((0, "the tree ends early".to_string()))?;
+        let invalid = |message: String| (n, message);
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (leading, fields) = match (response, fields.as_slice()) {
+            (true, [leading, rest @ ..]) => (Some(*leading), rest),
+            _ => (None, fields.as_slice()),
+        };
+        let [guess, branches, answers] = fields else {
+            return Err(invalid(format!("expected a {}guess, a branch count and an answer count",
+                if response { "response, " } else { "" })));
+        };
+        let guess = Word::new(guess).map_err(|e| invalid(e.to_string()))?;
+        let feedback = leading
+            .map(|r| r.parse::<Feedback>().map_err(|e: ParseFeedbackError| invalid(e.to_string())))
+            .transpose()?;
+        let branches: usize = branches.parse().map_err(|_| invalid(format!("{} is not a branch count", branches)))?;
+        let answers: usize = answers.parse().map_err(|_| invalid(format!("{} is not an answer count", answers)))?;
+        let mut node = Node { guess, solves: false, branches: Vec::new() };
+        for _ in 0..branches {
+            let (response, child) = Node::read(lines, true)?;
+            let response = response
+                .filter(|response| response.len() == guess.len() && child.guess.len() == guess.len())
+                .ok_or_else(|| invalid("the words and responses are not all of the same length".to_string()))?;
+            node.branches.push((response, child));
+        }
+        let below: usize = node.branches.iter().map(|(_, child)| child.stats().0).sum();
+        match answers.checked_sub(below) {
+            Some(0) if branches > 0 => {}
+            Some(1) => node.solves = true,
+            _ => return Err(invalid(format!("{} answers do not add up with the branches", answers))),
+        }
+        Ok((feedback, node))
+    }
+}
+
+/// the guess to play in every state of the game, worked out ahead of time by [`TreeSearch`].
+///
+/// It is saved as text, one node per line in depth first order: the response that leads to the
+/// node (left out for the first guess), the guess, the number of responses that carry on from
+/// it and the number of answers that reach it:
+///
+/// ```text
+/// # 6 answers, 13 guesses in total, 3 at most
+/// khamp 5 6
+/// NNNNN bills 1 2
+/// NGGGG fills 0 1
+/// NNNNY pills 0 1
+/// NNNYN mills 0 1
+/// NYNNN hills 0 1
+/// GNNNN kills 0 1
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTree {
+    root: Node,
+    /// the number of answers the tree solves.
+    answers: usize,
+    /// the guesses it takes to solve every answer, added up.
+    total: usize,
+    /// the most guesses it takes to solve an answer.
+    most: usize,
+}
+
+impl DecisionTree {
+    fn new(root: Node) -> DecisionTree {
+        let (answers, total, most) = root.stats();
+        DecisionTree { root, answers, total, most }
+    }
+
+    /// the first word to play.
+    pub fn first_guess(&self) -> Word {
+        self.root.guess
+    }
+
+    /// the number of answers the tree solves.
+    pub fn answers(&self) -> usize {
+        self.answers
+    }
+
+    /// the guesses it takes to solve every answer, added up.
+    pub fn total_guesses(&self) -> usize {
+        self.total
+    }
+
+    /// the most guesses it takes to solve an answer.
+    pub fn most_guesses(&self) -> usize {
+        self.most
+    }
+
+    /// the average number of guesses over the answers.
+    pub fn average(&self) -> f64 {
+        self.total as f64 / self.answers.max(1) as f64
+    }
+
+    /// the word to play after the given guesses and responses, or None when they left the tree.
+    pub fn suggest(&self, steps: &[(Word, Feedback)]) -> Option<Word> {
+        let mut node = &self.root;
+        for (guess, response) in steps {
+            if *guess != node.guess {
+                return None;
+            }
+            node = &node.branches.iter().find(|(r, _)| r == response)?.1;
+        }
+        Some(node.guess)
+    }
+
+    /// writes the tree to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let write = || -> io::Result<()> {
+            let mut out = BufWriter::new(File::create(path)?);
+            writeln!(out, "# {} answers, {} guesses in total, {} at most", self.answers, self.total, self.most)?;
+            self.root.write(&mut out, None)?;
+            out.flush()
+        };
+        write().map_err(|e| Error::file(path, e))
+    }
+
+    /// reads a tree written by [`DecisionTree::save`]. Lines starting with `#` are comments.
+    pub fn load(path: &Path) -> Result<DecisionTree, Error> {
+        let file = File::open(path).map_err(|e| Error::file(path, e))?;
+        let mut lines = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| Error::file(path, e))?;
+            if !line.trim().is_empty() && !line.starts_with('#') {
+                lines.push((i + 1, line));
+            }
+        }
+        let mut lines = lines.into_iter();
+        let invalid = |(n, message): (usize, String)| match n {
+            0 => Error::Parse(format!("{}: {}", path.display(), message)),
+            _ => Error::Parse(format!("{} line {}: {}", path.display(), n, message)),
+        };
+        let (_, root) = Node::read(&mut lines, false).map_err(invalid)?;
+        if let Some(extra) = lines.next() {
+            return Err(invalid((extra.0, "the tree has ended already".to_string())));
+        }
+        Ok(DecisionTree::new(root))
+    }
+}
+
+/// what is known about solving a set of candidates with a number of guesses left.
+#[derive(Debug, Clone, Copy)]
+enum Outcome {
+    /// the fewest guesses in total, and the guess (by position) to play for them.
+    Best(u32, usize),
+    /// no tree takes fewer guesses in total than this.
+    AtLeast(u32),
+}
+
+/// works out the decision tree that solves every answer of a [`PatternMatrix`] in the fewest
+/// guesses, by a depth first search that drops a guess as soon as it cannot beat the best one
+/// found so far. The sets of candidates already solved are remembered, as different guesses often
+/// lead to the same ones.
+///
+/// Trying every guess in every state gives a provably optimal tree, which takes a while for long
+/// word lists; [`TreeSearch::with_breadth`] trades that for speed.
+#[derive(Debug)]
+pub struct TreeSearch<'a> {
+    patterns: &'a PatternMatrix,
+    objective: Objective,
+    /// the number of guesses tried in each state, the most promising first. None for all.
+    breadth: Option<usize>,
+    /// the first guess (by position), when it is forced.
+    start: Option<usize>,
+    /// the position in the guesses of each answer, if it is a guess.
+    answer_guesses: Vec<Option<usize>>,
+    /// what has been found out for each set of candidates (positions in the answers, in order)
+    /// and number of guesses left.
+    memo: HashMap<(Vec<u32>, usize), Outcome>,
+}
+
+impl<'a> TreeSearch<'a> {
+    pub fn new(patterns: &'a PatternMatrix) -> TreeSearch<'a> {
+        let answer_guesses = patterns.answers().iter()
+            .map(|answer| patterns.guesses().iter().position(|guess| guess == answer))
+            .collect();
+        TreeSearch {
+            patterns,
+            objective: Objective::default(),
+            breadth: None,
+            start: None,
+            answer_guesses,
+            memo: HashMap::new(),
+        }
+    }
+
+    pub fn with_objective(mut self, objective: Objective) -> TreeSearch<'a> {
+        self.objective = objective;
+        self
+    }
+
+    /// only tries the `breadth` guesses that leave the fewest candidates on average in each
+    /// state. The tree is then the best one made of those guesses, not necessarily the best one.
+    pub fn with_breadth(mut self, breadth: usize) -> TreeSearch<'a> {
+        self.breadth = Some(breadth);
+        self
+    }
+
+    /// plays `start` first, if it is one of the guesses.
+    pub fn with_start(mut self, start: &Word) -> TreeSearch<'a> {
+        self.start = self.patterns.guesses().iter().position(|guess| guess == start);
+        self
+    }
+
+    /// the best tree, or None when there are no answers or no tree solves them all within
+    /// [`GIVE_UP_AFTER`] guesses.
+    pub fn build(mut self) -> Option<DecisionTree> {
+        let all: Vec<u32> = (0..self.patterns.answers().len() as u32).collect();
+        if all.is_empty() {
+            return None;
+        }
+        // the fewest guesses in total, whatever the depth; or else the total for the smallest
+        // depth that solves every answer.
+        let depths = match self.objective {
+            Objective::Total => GIVE_UP_AFTER..=GIVE_UP_AFTER,
+            Objective::Most => 1..=GIVE_UP_AFTER,
+        };
+        for depth in depths {
+            let solved = match self.start {
+                Some(start) => self.evaluate(&all, depth, start, INFEASIBLE),
+                None => self.search(&all, depth, INFEASIBLE),
+            };
+            if solved.is_some() {
+                let root = self.node(&all, depth, self.start);
+                return Some(DecisionTree::new(root));
+            }
+        }
+        None
+    }
+
+    /// the fewest guesses that solving `set` within `depth` guesses can take in total.
+    fn lower_bound(size: usize, depth: usize) -> u32 {
+        match (size, depth) {
+            (0, _) => 0,
+            (_, 0) => INFEASIBLE,
+            (1, _) => 1,
+            (_, 1) => INFEASIBLE,
+            // one candidate is guessed straight away, the others take two guesses at least.
+            _ => 2 * size as u32 - 1,
+        }
+    }
+
+    /// the guess for sets so small that playing their first candidate is known to be best.
+    fn obvious(&self, set: &[u32], depth: usize) -> Option<usize> {
+        match set.len() {
+            1 | 2 if depth >= set.len() => self.answer_guesses[set[0] as usize],
+            _ => None,
+        }
+    }
+
+    /// the fewest guesses that solving `set` within `depth` guesses takes in total, if that is
+    /// less than `bound`.
+    fn search(&mut self, set: &[u32], depth: usize, bound: u32) -> Option<u32> {
+        let least = TreeSearch::lower_bound(set.len(), depth);
+        if least >= bound {
+            return None;
+        }
+        if self.obvious(set, depth).is_some() {
+            return Some(least);
+        }
+        let key = (set.to_vec(), depth);
+        match self.memo.get(&key) {
+            Some(Outcome::Best(total, _)) => return (*total < bound).then_some(*total),
+            Some(Outcome::AtLeast(total)) if *total >= bound => return None,
+            _ => {}
+        }
+        let solved = pattern_count(self.patterns.answers()[0].len()) - 1;
+        let candidates: Vec<usize> = set.iter().map(|a| *a as usize).collect();
+        // the lower bound, the sum of the squared buckets and whether the guess is not a
+        // candidate, for every guess that tells some candidates apart.
+        let mut options = Vec::new();
+        for guess in 0..self.patterns.guesses().len() {
+            let buckets = self.patterns.buckets(guess, &candidates);
+            let candidate = buckets[solved] > 0;
+            if !candidate && buckets.contains(&(set.len() as u32)) {
+                continue;
+            }
+            let mut bound = set.len() as u32;
+            for (pattern, count) in buckets.iter().enumerate() {
+                if pattern != solved {
+                    bound = bound.saturating_add(TreeSearch::lower_bound(*count as usize, depth - 1));
+                }
+            }
+            if bound != INFEASIBLE {
+                options.push((bound, buckets.iter().map(|count| count * count).sum::<u32>(), !candidate, guess));
+            }
+        }
+        if let Some(breadth) = self.breadth {
+            options.sort_by_key(|(_, squares, not_candidate, _)| (*squares, *not_candidate));
+            options.truncate(breadth);
+        }
+        options.sort();
+        let mut best = None;
+        let mut limit = bound;
+        for (least, _, _, guess) in options {
+            if least >= limit {
+                break;
+            }
+            if let Some(total) = self.evaluate(set, depth, guess, limit) {
+                best = Some((total, guess));
+                limit = total;
+            }
+        }
+        let outcome = match best {
+            Some((total, guess)) => Outcome::Best(total, guess),
+            None => Outcome::AtLeast(bound),
+        };
+        self.memo.insert(key, outcome);
+        best.map(|(total, _)| total)
+    }
+
+    /// the candidates of `set` split by their response to `guess`, leaving out the one it solves.
+    fn split(&self, set: &[u32], guess: usize) -> Vec<(u16, Vec<u32>)> {
+        let row = self.patterns.row(guess);
+        let solved = (pattern_count(self.patterns.answers()[0].len()) - 1) as u16;
+        let mut responses: Vec<(u16, u32)> = set.iter().map(|a| (row[*a as usize], *a)).collect();
+        responses.sort();
+        let mut buckets: Vec<(u16, Vec<u32>)> = Vec::new();
+        for (pattern, answer) in responses {
+            match buckets.last_mut() {
+                Some((last, bucket)) if *last == pattern => bucket.push(answer),
+                _ if pattern == solved => {}
+                _ => buckets.push((pattern, vec![answer])),
+            }
+        }
+        buckets
+    }
+
+    /// the guesses that playing `guess` and then the best guesses takes to solve `set` within
+    /// `depth` guesses, in total, if that is less than `bound`.
+    fn evaluate(&mut self, set: &[u32], depth: usize, guess: usize, bound: u32) -> Option<u32> {
+        let mut buckets = self.split(set, guess);
+        if buckets.iter().any(|(_, bucket)| bucket.len() == set.len()) {
+            return None;
+        }
+        let mut total = set.len() as u32;
+        for (_, bucket) in &buckets {
+            total = total.saturating_add(TreeSearch::lower_bound(bucket.len(), depth - 1));
+        }
+        if total >= bound {
+            return None;
+        }
+        // the largest sets are the likeliest to go over the bound.
+        buckets.sort_by_key(|(_, bucket)| std::cmp::Reverse(bucket.len()));
+        for (_, bucket) in &buckets {
+            let least = TreeSearch::lower_bound(bucket.len(), depth - 1);
+            let solved = self.search(bucket, depth - 1, bound - (total - least))?;
+            total += solved - least;
+        }
+        Some(total)
+    }
+
+    /// the tree for `set` with `depth` guesses left, once the search has gone through it.
+    fn node(&self, set: &[u32], depth: usize, guess: Option<usize>) -> Node {
+        let guess = guess
+            .or_else(|| self.obvious(set, depth))
+            .or_else(|| match self.memo.get(&(set.to_vec(), depth)) {
+                Some(Outcome::Best(_, guess)) => Some(*guess),
+                _ => None,
+            })
+            .expect("the search solved every set on the way");
+        let word = self.patterns.guesses()[guess];
+        let branches = self.split(set, guess).into_iter()
+            .map(|(pattern, bucket)| (Feedback::from_code(pattern as usize, word.len()), self.node(&bucket, depth - 1, None)))
+            .collect();
+        let solves = set.iter().any(|a| self.patterns.answers()[*a as usize] == word);
+        Node { guess: word, solves, branches }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::WordCollection;
+    use crate::compare::wordle_compare;
+
+    fn words() -> (WordCollection, WordCollection) {
+        let answers = WordCollection::from_lines(["bills", "fills", "hills", "kills", "mills", "pills"], 5);
+        let guesses = answers.union(&WordCollection::from_lines(["khamp"], 5));
+        (answers, guesses)
+    }
+
+    #[test]
+    fn test_build() {
+        let (answers, guesses) = words();
+        let patterns = PatternMatrix::build(&guesses, &answers);
+        for objective in [Objective::Total, Objective::Most] {
+            let tree = TreeSearch::new(&patterns).with_objective(objective).build().unwrap();
+            assert_eq!(tree.first_guess(), Word::new("khamp").unwrap());
+            assert_eq!((tree.answers(), tree.total_guesses(), tree.most_guesses()), (6, 13, 3));
+            let mut total = 0;
+            for answer in answers.words() {
+                let mut steps = Vec::new();
+                while let Some(guess) = tree.suggest(&steps) {
+                    let response = wordle_compare(&answer.to_string(), &guess.to_string()).unwrap();
+                    steps.push((guess, response));
+                    if response.is_solved() {
+                        break;
+                    }
+                }
+                assert!(steps.last().is_some_and(|(guess, _)| guess == answer));
+                total += steps.len();
+            }
+            assert_eq!(total, 13);
+        }
+        let forced = TreeSearch::new(&patterns).with_start(&Word::new("bills").unwrap()).build().unwrap();
+        assert_eq!(forced.first_guess(), Word::new("bills").unwrap());
+        assert_eq!(forced.total_guesses(), 16);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let (answers, guesses) = words();
+        let patterns = PatternMatrix::build(&guesses, &answers);
+        let tree = TreeSearch::new(&patterns).build().unwrap();
+        let path = std::env::temp_dir().join(format!("wordlers-tree-{}.txt", std::process::id()));
+        tree.save(&path).unwrap();
+        assert_eq!(DecisionTree::load(&path).unwrap(), tree);
+        std::fs::write(&path, "khamp 1 6\nNNNNN bills 0 1\n").unwrap();
+        assert!(matches!(DecisionTree::load(&path), Err(Error::Parse(_))));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::charset::CharSet;
+use crate::error::Error;
+
+/// the longest word we can hold.
+pub const MAX_WORD_LEN: usize = 10;
+
+/// a word of up to [`MAX_WORD_LEN`] lower case letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Word {
+    /// the letters, as ascii bytes. Only the first `len` are used, the rest are zero.
+    letters : [u8; MAX_WORD_LEN],
+    /// the number of letters in the word.
+    len : u8
+}
+
+impl Word {
+    /// the word spelt by `str`, which must be 1 to [`MAX_WORD_LEN`] lower case letters.
+    pub fn new(str : &str) -> Result<Word, Error> {
+        if str.is_empty() || str.len() > MAX_WORD_LEN || !str.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(Error::Invalid(format!("{} is not a word of 1 to {} lower case letters", str, MAX_WORD_LEN)));
+        }
+        let mut letters = [0; MAX_WORD_LEN];
+        letters[..str.len()].copy_from_slice(str.as_bytes());
+        Ok(Word {
+            letters,
+            len : str.len() as u8
+        })
+    }
+
+    /// the number of letters in the word.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// the letters of the word as ascii bytes, in order.
+    pub fn letters(&self) -> &[u8] {
+        &self.letters[..self.len()]
+    }
+
+    /// the letters of the word, in order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.letters().iter().map(|b| *b as char)
+    }
+
+    pub fn has_repeated_chars(&self) -> bool {
+        let letters = self.letters();
+        (1..letters.len()).any(|i| letters[..i].contains(&letters[i]))
+    }
+
+    /// sum of the scores of each letter, with a penalty for words that repeat a letter.
+    pub fn score(&self, scores: &[usize]) -> usize {
+        let raw_score: usize = self.letters().iter().map(|b| scores[(b - b'a') as usize]).sum();
+        if self.has_repeated_chars() {
+            raw_score.saturating_sub(20)
+        } else {
+            raw_score
+        }
+    }
+
+    pub fn to_char_set(self) -> CharSet {
+        let mut char_set = CharSet::new();
+        for c in self.chars() {
+            char_set.add(c);
+        }
+        char_set
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.chars() {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let word = Word::new("crane").unwrap();
+        assert_eq!(word.len(), 5);
+        assert_eq!(word.to_string(), "crane");
+        assert!(Word::new("").is_err());
+        assert!(Word::new("Crane").is_err());
+        assert!(Word::new("abcdefghijk").is_err());
+    }
+}